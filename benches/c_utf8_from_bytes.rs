@@ -13,3 +13,12 @@ fn from_bytes(b: &mut Bencher) {
         black_box(CUtf8::from_bytes(s).unwrap());
     });
 }
+
+#[bench]
+fn from_bytes_ascii(b: &mut Bencher) {
+    let s = "The quick brown fox jumps over the lazy dog\0";
+    b.iter(|| {
+        let s = black_box(s.as_bytes());
+        black_box(CUtf8::from_bytes(s).unwrap());
+    });
+}
@@ -0,0 +1,190 @@
+use core::ffi::{c_char, c_int, CStr};
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::Utf8Error;
+
+use crate::c_utf8::CUtf8;
+
+/// An iterator over the arguments passed to a C entry point, as validated
+/// UTF-8 C strings.
+///
+/// This is normally obtained through the [`c_main!`](macro.c_main.html)
+/// macro.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use std::ffi::c_char;
+/// use c_utf8::CArgs;
+///
+/// let argv = [b"prog\0".as_ptr() as *const c_char, b"-v\0".as_ptr().cast()];
+/// let args = unsafe { CArgs::from_raw(2, argv.as_ptr()) }.unwrap();
+///
+/// let args: Vec<&str> = args.map(|arg| arg.as_str()).collect();
+/// assert_eq!(args, ["prog", "-v"]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct CArgs<'a> {
+    argv: *const *const c_char,
+    len: usize,
+    marker: PhantomData<&'a CUtf8>,
+}
+
+/// An iterator over the `KEY=VALUE` environment variables passed to a C entry
+/// point, as validated UTF-8 C strings.
+///
+/// This is normally obtained through the [`c_main!`](macro.c_main.html)
+/// macro.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use std::ffi::c_char;
+/// use std::ptr;
+/// use c_utf8::CEnv;
+///
+/// let envp = [b"LANG=C\0".as_ptr() as *const c_char, ptr::null()];
+/// let mut env = unsafe { CEnv::from_raw(envp.as_ptr()) }.unwrap();
+///
+/// assert_eq!(env.next().map(|var| var.as_str()), Some("LANG=C"));
+/// assert_eq!(env.next(), None);
+/// ```
+#[derive(Clone, Copy)]
+pub struct CEnv<'a> {
+    envp: *const *const c_char,
+    marker: PhantomData<&'a CUtf8>,
+}
+
+// SAFETY: The pointers are only used to produce `&'a CUtf8` references
+unsafe impl<'a> Send for CArgs<'a> {}
+unsafe impl<'a> Sync for CArgs<'a> {}
+unsafe impl<'a> Send for CEnv<'a> {}
+unsafe impl<'a> Sync for CEnv<'a> {}
+
+/// Returns the C string at `ptr` without checking for UTF-8.
+#[inline]
+unsafe fn c_utf8_at<'a>(ptr: *const c_char) -> &'a CUtf8 {
+    CUtf8::from_c_str_unchecked(CStr::from_ptr(ptr))
+}
+
+impl<'a> CArgs<'a> {
+    /// Creates an iterator over the `argc` C strings in `argv`, checking that
+    /// each of them is valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// If `argc` is positive, `argv` must point to an array of at least `argc`
+    /// pointers to valid nul-terminated C strings, all of which outlive `'a`.
+    pub unsafe fn from_raw(
+        argc: c_int,
+        argv: *const *const c_char,
+    ) -> Result<CArgs<'a>, Utf8Error> {
+        let len = if argc > 0 && !argv.is_null() {
+            argc as usize
+        } else {
+            0
+        };
+        for i in 0..len {
+            CUtf8::from_ptr(*argv.add(i))?;
+        }
+        Ok(CArgs {
+            argv,
+            len,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<'a> Iterator for CArgs<'a> {
+    type Item = &'a CUtf8;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a CUtf8> {
+        if self.len == 0 {
+            return None;
+        }
+        unsafe {
+            let arg = c_utf8_at(*self.argv);
+            self.argv = self.argv.add(1);
+            self.len -= 1;
+            Some(arg)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a> DoubleEndedIterator for CArgs<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a CUtf8> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe { Some(c_utf8_at(*self.argv.add(self.len))) }
+    }
+}
+
+impl<'a> ExactSizeIterator for CArgs<'a> {}
+
+impl<'a> fmt::Debug for CArgs<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+impl<'a> CEnv<'a> {
+    /// Creates an iterator over the null-terminated array of C strings in
+    /// `envp`, checking that each of them is valid UTF-8.
+    ///
+    /// A null `envp` is treated as an empty environment.
+    ///
+    /// # Safety
+    ///
+    /// If `envp` is non-null, it must point to an array of pointers to valid
+    /// nul-terminated C strings that is terminated by a null pointer, all of
+    /// which outlive `'a`.
+    pub unsafe fn from_raw(envp: *const *const c_char) -> Result<CEnv<'a>, Utf8Error> {
+        let mut ptr = envp;
+        while !ptr.is_null() && !(*ptr).is_null() {
+            CUtf8::from_ptr(*ptr)?;
+            ptr = ptr.add(1);
+        }
+        Ok(CEnv {
+            envp,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<'a> Iterator for CEnv<'a> {
+    type Item = &'a CUtf8;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a CUtf8> {
+        if self.envp.is_null() {
+            return None;
+        }
+        unsafe {
+            let var = *self.envp;
+            if var.is_null() {
+                return None;
+            }
+            self.envp = self.envp.add(1);
+            Some(c_utf8_at(var))
+        }
+    }
+}
+
+impl<'a> fmt::Debug for CEnv<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
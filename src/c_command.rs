@@ -0,0 +1,336 @@
+use alloc::string::String;
+use alloc::{vec, vec::Vec};
+use core::ffi::c_char;
+use core::fmt;
+use core::ptr;
+
+use crate::c_utf8::CUtf8;
+use crate::c_utf8_buf::CUtf8Buf;
+use crate::error::{check_no_nul, Error};
+
+/// The error for setting an invalid environment variable with
+/// [`CCommand::env`](struct.CCommand.html#method.env).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnvError {
+    /// An error indicating that the variable name was empty or contained `=`.
+    InvalidKey,
+    /// An error indicating that the name or value contained a nul byte.
+    InteriorNul {
+        /// The byte position of the nul byte in the `KEY=VALUE` string.
+        position: usize,
+    },
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EnvError::InvalidKey => "Environment variable name is empty or contains '='".fmt(f),
+            EnvError::InteriorNul { position } => {
+                write!(f, "Interior nul byte at position {}", position)
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "error_in_core"))]
+impl crate::error::StdError for EnvError {}
+
+/// An owned program path, argument vector, and environment as C strings, ready
+/// to be passed to `execve`, `posix_spawn`, and similar C APIs.
+///
+/// The pointers returned by [`program_ptr`], [`argv`], and [`envp`] remain
+/// valid until `self` is either modified or dropped. The argument and
+/// environment arrays are always terminated by a null pointer.
+///
+/// Unlike [`std::process::Command`], the environment starts out empty rather
+/// than inheriting from the current process.
+///
+/// C would silently truncate strings at an interior nul byte, and would read a
+/// variable name containing `=` as a different variable, so such input is
+/// rejected when it is added.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8;
+/// use std::ffi::CStr;
+/// use c_utf8::{CCommand, EnvError, Error};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut cmd = CCommand::new(c_utf8!("/bin/echo"))?;
+/// cmd.arg("Hello,")?.arg("world!")?.env("LANG", "C.UTF-8")?;
+///
+/// // Would be passed as `execve(cmd.program_ptr(), cmd.argv(), cmd.envp())`
+/// unsafe {
+///     let argv = cmd.argv();
+///     assert_eq!(CStr::from_ptr(*argv.add(0)).to_str(), Ok("/bin/echo"));
+///     assert_eq!(CStr::from_ptr(*argv.add(2)).to_str(), Ok("world!"));
+///     assert!((*argv.add(3)).is_null());
+///
+///     let envp = cmd.envp();
+///     assert_eq!(CStr::from_ptr(*envp).to_str(), Ok("LANG=C.UTF-8"));
+///     assert!((*envp.add(1)).is_null());
+/// }
+///
+/// assert_eq!(cmd.arg("a\0b").unwrap_err(), Error::InteriorNul { position: 1 });
+/// assert_eq!(cmd.env("A=B", "C").unwrap_err(), EnvError::InvalidKey);
+/// assert_eq!(
+///     cmd.env("A", "b\0").unwrap_err(),
+///     EnvError::InteriorNul { position: 3 },
+/// );
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`program_ptr`]: #method.program_ptr
+/// [`argv`]: #method.argv
+/// [`envp`]: #method.envp
+/// [`std::process::Command`]: https://doc.rust-lang.org/std/process/struct.Command.html
+pub struct CCommand {
+    program: CUtf8Buf,
+    args: Vec<CUtf8Buf>,
+    env: Vec<CUtf8Buf>,
+    // Pointers into the heap buffers of `args` and `env`, which do not move
+    // when the buffers themselves are moved
+    argv: Vec<*const c_char>,
+    envp: Vec<*const c_char>,
+}
+
+// SAFETY: The raw pointers only refer to data owned by `CCommand`
+unsafe impl Send for CCommand {}
+unsafe impl Sync for CCommand {}
+
+impl Clone for CCommand {
+    fn clone(&self) -> CCommand {
+        let mut cmd = CCommand::with_program(self.program.clone());
+        for arg in &self.args {
+            cmd.push_arg(arg.clone());
+        }
+        for var in &self.env {
+            cmd.push_env(var.clone());
+        }
+        cmd
+    }
+}
+
+impl fmt::Debug for CCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CCommand")
+            .field("program", &self.program)
+            .field("args", &self.args)
+            .field("env", &self.env)
+            .finish()
+    }
+}
+
+impl CCommand {
+    /// Creates a new command for `program`, which is also used as the first
+    /// argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InteriorNul`] if `program` contains a nul byte.
+    ///
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    pub fn new<P: Into<CUtf8Buf>>(program: P) -> Result<CCommand, Error> {
+        let program = program.into();
+        check_no_nul(program.as_bytes())?;
+        let mut cmd = CCommand::with_program(program.clone());
+        cmd.push_arg(program);
+        Ok(cmd)
+    }
+
+    fn with_program(program: CUtf8Buf) -> CCommand {
+        CCommand {
+            program,
+            args: Vec::new(),
+            env: Vec::new(),
+            argv: vec![ptr::null()],
+            envp: vec![ptr::null()],
+        }
+    }
+
+    /// Appends an argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InteriorNul`] if `arg` contains a nul byte, in which
+    /// case it is not added.
+    ///
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    pub fn arg<A: Into<CUtf8Buf>>(&mut self, arg: A) -> Result<&mut CCommand, Error> {
+        let arg = arg.into();
+        check_no_nul(arg.as_bytes())?;
+        self.push_arg(arg);
+        Ok(self)
+    }
+
+    /// Appends multiple arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InteriorNul`] for the first argument that contains a
+    /// nul byte. The arguments before it are kept.
+    ///
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    pub fn args<I>(&mut self, args: I) -> Result<&mut CCommand, Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<CUtf8Buf>,
+    {
+        for arg in args {
+            self.arg(arg)?;
+        }
+        Ok(self)
+    }
+
+    /// Sets the first argument, which defaults to the program path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InteriorNul`] if `arg0` contains a nul byte, in which
+    /// case the first argument is left unchanged.
+    ///
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    pub fn arg0<A: Into<CUtf8Buf>>(&mut self, arg0: A) -> Result<&mut CCommand, Error> {
+        let arg0 = arg0.into();
+        check_no_nul(arg0.as_bytes())?;
+        self.argv[0] = arg0.as_ptr();
+        self.args[0] = arg0;
+        Ok(self)
+    }
+
+    fn push_arg(&mut self, arg: CUtf8Buf) {
+        // Insert before the trailing null pointer
+        self.argv.pop();
+        self.argv.push(arg.as_ptr());
+        self.argv.push(ptr::null());
+        self.args.push(arg);
+    }
+
+    /// Sets the environment variable `key` to `value`, replacing any previous
+    /// value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EnvError::InvalidKey`] if `key` is empty or contains `=`, or
+    /// [`EnvError::InteriorNul`] if either contains a nul byte, with its
+    /// position in the `KEY=VALUE` string. Nothing is changed in either case.
+    ///
+    /// [`EnvError::InvalidKey`]:  enum.EnvError.html#variant.InvalidKey
+    /// [`EnvError::InteriorNul`]: enum.EnvError.html#variant.InteriorNul
+    pub fn env<K, V>(&mut self, key: K, value: V) -> Result<&mut CCommand, EnvError>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let (key, value) = (key.as_ref(), value.as_ref());
+        if key.is_empty() || key.contains('=') {
+            return Err(EnvError::InvalidKey);
+        }
+
+        let mut var = String::with_capacity(key.len() + value.len() + 2);
+        var.push_str(key);
+        var.push('=');
+        var.push_str(value);
+        if let Some(position) = var.bytes().position(|b| b == 0) {
+            return Err(EnvError::InteriorNul { position });
+        }
+        let var = CUtf8Buf::from_string(var);
+
+        match self.env_index(key) {
+            Some(i) => {
+                self.envp[i] = var.as_ptr();
+                self.env[i] = var;
+            }
+            None => self.push_env(var),
+        }
+        Ok(self)
+    }
+
+    /// Sets multiple environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`env`](#method.env) for the first invalid
+    /// variable. The variables before it are kept.
+    pub fn envs<I, K, V>(&mut self, vars: I) -> Result<&mut CCommand, EnvError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (key, value) in vars {
+            self.env(key, value)?;
+        }
+        Ok(self)
+    }
+
+    /// Removes the environment variable `key`.
+    pub fn env_remove<K: AsRef<str>>(&mut self, key: K) -> &mut CCommand {
+        if let Some(i) = self.env_index(key.as_ref()) {
+            self.env.remove(i);
+            self.envp.remove(i);
+        }
+        self
+    }
+
+    /// Removes all environment variables.
+    pub fn env_clear(&mut self) -> &mut CCommand {
+        self.env.clear();
+        self.envp.clear();
+        self.envp.push(ptr::null());
+        self
+    }
+
+    fn env_index(&self, key: &str) -> Option<usize> {
+        self.env.iter().position(|var| {
+            let var = var.as_bytes();
+            var.len() > key.len() && var.starts_with(key.as_bytes()) && var[key.len()] == b'='
+        })
+    }
+
+    fn push_env(&mut self, var: CUtf8Buf) {
+        self.envp.pop();
+        self.envp.push(var.as_ptr());
+        self.envp.push(ptr::null());
+        self.env.push(var);
+    }
+
+    /// Returns the program path.
+    #[inline]
+    pub fn program(&self) -> &CUtf8 {
+        &self.program
+    }
+
+    /// Returns the arguments, including the first one.
+    #[inline]
+    pub fn get_args(&self) -> &[CUtf8Buf] {
+        &self.args
+    }
+
+    /// Returns the environment as `KEY=VALUE` strings.
+    #[inline]
+    pub fn get_envs(&self) -> &[CUtf8Buf] {
+        &self.env
+    }
+
+    /// Returns a pointer to the program path.
+    #[inline]
+    pub fn program_ptr(&self) -> *const c_char {
+        self.program.as_ptr()
+    }
+
+    /// Returns a pointer to the null-terminated array of argument pointers.
+    #[inline]
+    pub fn argv(&self) -> *const *const c_char {
+        self.argv.as_ptr()
+    }
+
+    /// Returns a pointer to the null-terminated array of `KEY=VALUE`
+    /// environment pointers.
+    #[inline]
+    pub fn envp(&self) -> *const *const c_char {
+        self.envp.as_ptr()
+    }
+}
@@ -0,0 +1,137 @@
+use core::ffi::{c_char, c_void};
+use core::fmt;
+
+/// A [`fmt::Write`] adapter that sends each line written to it to a C output
+/// callback, such as those accepted by C logging APIs.
+///
+/// Text is staged in an internal buffer of `N` bytes, including the trailing
+/// nul byte, and passed to the callback as a nul-terminated UTF-8 string
+/// whenever a `\n` is written. Lines are passed without their `\n`. Lines that
+/// don't fit in the buffer are split on a character boundary, and any partial
+/// line is flushed when the writer is dropped.
+///
+/// No allocations are made, so this is available without the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::{c_char, c_void, CStr};
+/// use std::fmt::Write;
+/// use c_utf8::CLineWriter;
+///
+/// unsafe extern "C" fn collect(line: *const c_char, ctx: *mut c_void) {
+///     let lines = &mut *(ctx as *mut Vec<String>);
+///     lines.push(CStr::from_ptr(line).to_str().unwrap().to_owned());
+/// }
+///
+/// let mut lines = Vec::new();
+/// {
+///     let ctx = &mut lines as *mut Vec<String> as *mut c_void;
+///     let mut w: CLineWriter = unsafe { CLineWriter::new(collect, ctx) };
+///     write!(w, "x = {}\ny = {}", 1, 2).unwrap();
+/// }
+///
+/// assert_eq!(lines, ["x = 1", "y = 2"]);
+/// ```
+///
+/// [`fmt::Write`]: https://doc.rust-lang.org/core/fmt/trait.Write.html
+pub struct CLineWriter<const N: usize = 256> {
+    sink: unsafe extern "C" fn(*const c_char, *mut c_void),
+    ctx: *mut c_void,
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> CLineWriter<N> {
+    // The buffer must fit at least any one character and the nul byte
+    const CAPACITY_CHECK: () = assert!(N > 4, "`CLineWriter` buffer is too small");
+
+    /// Creates a new writer that passes each line along with `ctx` to `sink`.
+    ///
+    /// # Safety
+    ///
+    /// Calling `sink` with `ctx` and a pointer to a valid nul-terminated string
+    /// that only lives for the duration of the call must be sound for as long
+    /// as the writer is alive.
+    #[inline]
+    pub unsafe fn new(
+        sink: unsafe extern "C" fn(*const c_char, *mut c_void),
+        ctx: *mut c_void,
+    ) -> CLineWriter<N> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::CAPACITY_CHECK;
+        CLineWriter {
+            sink,
+            ctx,
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Passes any partially written line to the callback.
+    pub fn flush(&mut self) {
+        if self.len > 0 {
+            self.emit();
+        }
+    }
+
+    fn emit(&mut self) {
+        self.buf[self.len] = 0;
+        unsafe { (self.sink)(self.buf.as_ptr().cast(), self.ctx) };
+        self.len = 0;
+    }
+
+    fn push_str(&mut self, mut s: &str) {
+        while !s.is_empty() {
+            let room = N - 1 - self.len;
+            let mut end = s.len().min(room);
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            if end == 0 {
+                self.emit();
+                continue;
+            }
+            self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+            self.len += end;
+            s = &s[end..];
+        }
+    }
+}
+
+impl<const N: usize> fmt::Write for CLineWriter<N> {
+    /// Writes `s`, passing each completed line to the callback.
+    ///
+    /// Returns an error without writing anything if `s` contains a nul byte.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.as_bytes().contains(&0) {
+            return Err(fmt::Error);
+        }
+        let mut lines = s.split('\n');
+        if let Some(first) = lines.next() {
+            self.push_str(first);
+        }
+        for line in lines {
+            self.emit();
+            self.push_str(line);
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Debug for CLineWriter<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pending = unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) };
+        f.debug_struct("CLineWriter")
+            .field("ctx", &self.ctx)
+            .field("pending", &pending)
+            .finish()
+    }
+}
+
+impl<const N: usize> Drop for CLineWriter<N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
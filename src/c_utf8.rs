@@ -1,8 +1,14 @@
 use core::convert::TryFrom;
 use core::ffi::c_char;
 use core::ffi::CStr;
-use core::fmt;
-use core::str::{self, Utf8Error};
+use core::fmt::{self, Write};
+use core::hash::{Hash, Hasher};
+use core::mem::MaybeUninit;
+use core::ops::{Index, RangeFrom};
+#[cfg(feature = "pattern")]
+use core::str::pattern::{Pattern, StrSearcher};
+use core::str::{self, EncodeUtf16, Utf8Error};
+use core::{ptr, slice};
 
 #[cfg(feature = "std")]
 use std::ffi::OsStr;
@@ -11,6 +17,7 @@ use std::ffi::OsStr;
 use std::path::Path;
 
 use crate::error::Error;
+use crate::iter_packed::IterPacked;
 
 /// Like [`CStr`](https://doc.rust-lang.org/core/ffi/struct.CStr.html), except
 /// with the guarantee of being encoded as valid [UTF-8].
@@ -31,10 +38,57 @@ use crate::error::Error;
 ///   [`str`](https://doc.rust-lang.org/std/primitive.str.html) strings with
 ///   ease.
 ///
+/// # Layout
+///
+/// `CUtf8` is `#[repr(transparent)]` over `str`, and this is part of its
+/// public API. A `&CUtf8` has the same size, alignment, and pointer metadata
+/// as a `&str` whose bytes are those of
+/// [`as_bytes_with_nul`](#method.as_bytes_with_nul), and the data pointer can
+/// be passed directly to C as a `*const c_char`.
+///
+/// Casting a `&str` or `&[u8]` to `&CUtf8` is only sound if the bytes uphold
+/// the guarantees above; prefer [`from_bytes`](#method.from_bytes) over
+/// unsafe casts, as it already borrows the input without copying.
+///
+#[cfg_attr(
+    feature = "zerocopy",
+    doc = r#"
+With the `zerocopy` feature, a string borrowed from a device buffer can be
+viewed as bytes with its nul terminator:
+
+```
+# extern crate c_utf8;
+extern crate zerocopy;
+
+use c_utf8::CUtf8;
+use zerocopy::{AsBytes, Unaligned};
+
+fn assert_unaligned<T: Unaligned + ?Sized>() {}
+assert_unaligned::<CUtf8>();
+
+let dma = [b'e', b't', b'h', b'0', 0, 0xFF, 0xFF];
+let (name, _) = CUtf8::from_bytes_until_nul(&dma).unwrap();
+
+assert_eq!(AsBytes::as_bytes(name), b"eth0\0");
+# fn main() {}
+```
+"#
+)]
+///
 /// [UTF-8]: https://en.wikipedia.org/wiki/UTF-8
-#[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::AsBytes, zerocopy::Unaligned))]
+#[repr(transparent)]
 pub struct CUtf8(str);
 
+impl Hash for CUtf8 {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash the same way as `CStr` so that owned types can `Borrow<CStr>`
+        self.as_bytes_with_nul().hash(state);
+    }
+}
+
 impl<'a> TryFrom<&'a [u8]> for &'a CUtf8 {
     type Error = Error;
 
@@ -62,6 +116,30 @@ impl<'a> TryFrom<&'a str> for &'a CUtf8 {
     }
 }
 
+/// Returns the string without its trailing nul byte.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// let s: &str = c_utf8!("Hey").into();
+/// assert_eq!(s, "Hey");
+/// # }
+/// ```
+impl<'a> From<&'a CUtf8> for &'a str {
+    #[inline]
+    fn from(c: &CUtf8) -> &str {
+        c.as_str()
+    }
+}
+
+impl AsRef<CUtf8> for CUtf8 {
+    #[inline]
+    fn as_ref(&self) -> &CUtf8 {
+        self
+    }
+}
+
 impl AsRef<str> for CUtf8 {
     #[inline]
     fn as_ref(&self) -> &str {
@@ -99,17 +177,127 @@ impl AsRef<OsStr> for CUtf8 {
     }
 }
 
+/// Formats the string as a quoted `str` including its trailing nul byte, or
+/// as a C string literal with the alternate `{:#?}` flag.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// let s = c_utf8!("tab\there");
+///
+/// assert_eq!(format!("{:?}", s), r#""tab\there\0""#);
+/// assert_eq!(format!("{:#?}", s), r#"c"tab\there""#);
+/// # }
+/// ```
 impl fmt::Debug for CUtf8 {
-    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.as_str_with_nul().fmt(f)
+        if !f.alternate() {
+            return self.as_str_with_nul().fmt(f);
+        }
+        f.write_str("c\"")?;
+        for c in self.as_str().chars() {
+            // Like `str`, but without escaping `'`
+            match c {
+                '\'' => f.write_char(c)?,
+                c => write!(f, "{}", c.escape_debug())?,
+            }
+        }
+        f.write_char('"')
     }
 }
 
+/// Formats the string without its trailing nul byte, honoring the width,
+/// precision, fill, and alignment flags like `str` does.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// let s = c_utf8!("Hey");
+///
+/// assert_eq!(format!("[{:>6}]", s), "[   Hey]");
+/// assert_eq!(format!("[{:-<6.2}]", s), "[He----]");
+/// # }
+/// ```
 impl fmt::Display for CUtf8 {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.as_str().fmt(f)
+        f.pad(self.as_str())
+    }
+}
+
+/// Searches for the string without its trailing nul byte.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// let haystack = "key=value=other";
+///
+/// assert_eq!(haystack.find(c_utf8!("=")), Some(3));
+/// assert_eq!(haystack.replace(c_utf8!("value"), "v"), "key=v=other");
+/// assert_eq!(haystack.split(c_utf8!("=")).count(), 3);
+/// # }
+/// ```
+#[cfg(feature = "pattern")]
+impl<'a, 'b> Pattern<'a> for &'b CUtf8 {
+    type Searcher = StrSearcher<'a, 'b>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &'a str) -> StrSearcher<'a, 'b> {
+        self.as_str().into_searcher(haystack)
+    }
+
+    #[inline]
+    fn is_contained_in(self, haystack: &'a str) -> bool {
+        self.as_str().is_contained_in(haystack)
+    }
+
+    #[inline]
+    fn is_prefix_of(self, haystack: &'a str) -> bool {
+        self.as_str().is_prefix_of(haystack)
+    }
+
+    #[inline]
+    fn is_suffix_of(self, haystack: &'a str) -> bool {
+        self.as_str().is_suffix_of(haystack)
+    }
+
+    #[inline]
+    fn strip_prefix_of(self, haystack: &'a str) -> Option<&'a str> {
+        self.as_str().strip_prefix_of(haystack)
+    }
+
+    #[inline]
+    fn strip_suffix_of(self, haystack: &'a str) -> Option<&'a str> {
+        self.as_str().strip_suffix_of(haystack)
+    }
+}
+
+/// Returns the suffix of the string starting at a byte offset, which keeps the
+/// trailing nul byte.
+///
+/// # Panics
+///
+/// Panics if the offset is greater than [`len`](#method.len) or does not lie
+/// on a `char` boundary.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// let s = c_utf8!("https://example.com");
+///
+/// assert_eq!(s[8..].as_bytes_with_nul(), b"example.com\0");
+/// # }
+/// ```
+impl Index<RangeFrom<usize>> for CUtf8 {
+    type Output = CUtf8;
+
+    #[inline]
+    fn index(&self, range: RangeFrom<usize>) -> &CUtf8 {
+        self.split_at(range.start).1
     }
 }
 
@@ -131,22 +319,233 @@ impl CUtf8 {
 
     /// Returns a C string containing `bytes`, or an error if a nul byte is in
     /// an unexpected position or if the bytes are not encoded as UTF-8.
+    ///
+    /// This is done in a single pass over `bytes`, which skips over runs of
+    /// ASCII a word at a time.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::{CUtf8, Error};
+    ///
+    /// let s = CUtf8::from_bytes(b"Gr\xC3\xBC\xC3\x9Fe\0").unwrap();
+    /// assert_eq!(s.as_str(), "Grüße");
+    ///
+    /// assert!(matches!(CUtf8::from_bytes(b"inter\0ior\0"), Err(Error::InteriorNul { position: 5 })));
+    /// assert!(matches!(CUtf8::from_bytes(b"\xFF\0"), Err(Error::Utf8(_))));
+    /// ```
     #[inline]
     pub const fn from_bytes(bytes: &[u8]) -> Result<&CUtf8, Error> {
-        CUtf8::from_str(match str::from_utf8(bytes) {
-            Ok(it) => it,
-            Err(err) => return Err(Error::Utf8(err)),
-        })
+        match validate_with_nul(bytes) {
+            Ok(()) => Ok(unsafe { CUtf8::from_bytes_unchecked(bytes) }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns a mutable C string containing `bytes`, or an error if a nul
+    /// byte is in an unexpected position or if the bytes are not encoded as
+    /// UTF-8.
+    ///
+    /// This allows for modifying a C string in place in a buffer that is not
+    /// owned by a [`CUtf8Buf`](struct.CUtf8Buf.html), such as one on the
+    /// stack.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8;
+    ///
+    /// let mut buf = *b"Hello\0";
+    /// let s = CUtf8::from_bytes_mut(&mut buf).unwrap();
+    /// s.make_ascii_uppercase();
+    ///
+    /// assert_eq!(&buf, b"HELLO\0");
+    /// ```
+    #[inline]
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<&mut CUtf8, Error> {
+        validate_with_nul(bytes)?;
+        Ok(unsafe { CUtf8::from_str_unchecked_mut(str::from_utf8_unchecked_mut(bytes)) })
+    }
+
+    /// Returns a C string containing `bytes` up to and including the first nul
+    /// byte, along with the bytes that follow it.
+    ///
+    /// This is useful for fixed-size buffers filled in by C, such as
+    /// `char name[64]`, where the string may be followed by unspecified data.
+    /// Only the bytes before the nul byte need to be UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MissingNul`] if `bytes` contains no nul byte, or
+    /// [`Error::Utf8`] if the bytes before it are not encoded as UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8;
+    ///
+    /// let mut name = [0xFFu8; 12];
+    /// name[..6].copy_from_slice(b"eth0\0\0");
+    ///
+    /// let (s, rest) = CUtf8::from_bytes_until_nul(&name).unwrap();
+    ///
+    /// assert_eq!(s.as_bytes_with_nul(), b"eth0\0");
+    /// assert_eq!(rest.len(), 7);
+    ///
+    /// assert!(CUtf8::from_bytes_until_nul(b"no nul").is_err());
+    /// ```
+    ///
+    /// [`Error::MissingNul`]: enum.Error.html#variant.MissingNul
+    /// [`Error::Utf8`]:       enum.Error.html#variant.Utf8
+    pub fn from_bytes_until_nul(bytes: &[u8]) -> Result<(&CUtf8, &[u8]), Error> {
+        let nul = match bytes.iter().position(|&b| b == 0) {
+            Some(nul) => nul,
+            None => return Err(Error::MissingNul),
+        };
+        let (s, rest) = bytes.split_at(nul + 1);
+        str::from_utf8(&s[..nul])?;
+        Ok((unsafe { CUtf8::from_bytes_unchecked(s) }, rest))
+    }
+
+    /// Returns an iterator over the back-to-back nul-terminated strings in
+    /// `bytes`, without copying.
+    ///
+    /// This splits buffers such as `environ` blocks, `/proc/<pid>/cmdline`, or
+    /// `REG_MULTI_SZ` values. Each string is validated on its own, so an
+    /// invalid one yields an error and iteration continues after it. Trailing
+    /// bytes without a nul byte yield [`Error::MissingNul`].
+    ///
+    /// Lists that end with an empty string, like `REG_MULTI_SZ`, yield that
+    /// empty string too. Use `take_while` to stop at it.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8;
+    ///
+    /// let cmdline = b"ls\0-l\0/tmp\0";
+    /// let args: Vec<&str> = CUtf8::iter_packed(cmdline)
+    ///     .map(|arg| arg.unwrap().as_str())
+    ///     .collect();
+    ///
+    /// assert_eq!(args, ["ls", "-l", "/tmp"]);
+    /// ```
+    ///
+    /// Stopping at the empty string of a double-nul-terminated list:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8;
+    ///
+    /// let multi_sz = b"one\0two\0\0";
+    /// let count = CUtf8::iter_packed(multi_sz)
+    ///     .take_while(|s| s.as_ref().map_or(true, |s| !s.is_empty()))
+    ///     .count();
+    ///
+    /// assert_eq!(count, 2);
+    /// ```
+    ///
+    /// [`Error::MissingNul`]: enum.Error.html#variant.MissingNul
+    #[inline]
+    pub fn iter_packed(bytes: &[u8]) -> IterPacked {
+        IterPacked::new(bytes)
+    }
+
+    /// Returns a C string containing `bytes` with a leading UTF-8 byte order
+    /// mark (BOM) removed, or an error if a nul byte is in an unexpected
+    /// position or if the bytes are not encoded as UTF-8.
+    ///
+    /// Files read for C consumption often start with a BOM, which confuses
+    /// many C parsers. If `bytes` does not start with a BOM, this behaves
+    /// exactly like [`from_bytes`](#method.from_bytes).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8;
+    ///
+    /// let s = CUtf8::from_bytes_strip_bom(b"\xEF\xBB\xBFkey=value\0").unwrap();
+    /// assert_eq!(s.as_str(), "key=value");
+    ///
+    /// let s = CUtf8::from_bytes_strip_bom(b"key=value\0").unwrap();
+    /// assert_eq!(s.as_str(), "key=value");
+    /// ```
+    #[inline]
+    pub const fn from_bytes_strip_bom(bytes: &[u8]) -> Result<&CUtf8, Error> {
+        match bytes {
+            [0xEF, 0xBB, 0xBF, rest @ ..] => CUtf8::from_bytes(rest),
+            _ => CUtf8::from_bytes(bytes),
+        }
+    }
+
+    /// Returns a C string containing `bytes`, or an error if a nul byte is in
+    /// an unexpected position or if the bytes are not encoded as UTF-8.
+    ///
+    /// This is an alias of [`from_bytes`](#method.from_bytes) that matches the
+    /// naming of [`CStr::from_bytes_with_nul`]. Like it, this is a `const fn`,
+    /// so it can validate computed byte arrays, such as those generated by
+    /// build scripts, in `const` and `static` items.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8;
+    ///
+    /// const BYTES: [u8; 6] = *b"hello\0";
+    /// const HELLO: &CUtf8 = match CUtf8::from_bytes_with_nul(&BYTES) {
+    ///     Ok(s) => s,
+    ///     Err(_) => panic!("invalid C string"),
+    /// };
+    ///
+    /// assert_eq!(HELLO.as_str(), "hello");
+    /// assert!(CUtf8::from_bytes_with_nul(b"hello").is_err());
+    /// ```
+    ///
+    /// [`CStr::from_bytes_with_nul`]: https://doc.rust-lang.org/core/ffi/struct.CStr.html#method.from_bytes_with_nul
+    #[inline]
+    pub const fn from_bytes_with_nul(bytes: &[u8]) -> Result<&CUtf8, Error> {
+        CUtf8::from_bytes(bytes)
     }
 
     /// Returns the UTF-8 string if it is terminated by a nul byte.
+    ///
+    /// This is a `const fn`, so it can validate computed strings in `const` and
+    /// `static` items, not only literals passed to
+    /// [`c_utf8!`](macro.c_utf8.html).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8;
+    ///
+    /// const NAME: &CUtf8 = match CUtf8::from_str(concat!(env!("CARGO_PKG_NAME"), "\0")) {
+    ///     Ok(s) => s,
+    ///     Err(_) => panic!("missing nul byte"),
+    /// };
+    ///
+    /// assert_eq!(NAME.as_str(), "c_utf8");
+    /// assert!(CUtf8::from_str("no nul").is_err());
+    /// ```
     #[inline]
     #[allow(clippy::should_implement_trait)]
     pub const fn from_str(s: &str) -> Result<&CUtf8, Error> {
-        if let Some(b'0') = s.as_bytes().last() {
+        if let Some(0) = s.as_bytes().last() {
             unsafe { Ok(CUtf8::from_str_unchecked(s)) }
         } else {
-            Err(Error::Nul)
+            Err(Error::MissingNul)
         }
     }
 
@@ -155,11 +554,74 @@ impl CUtf8 {
     pub const fn from_c_str(c: &CStr) -> Result<&CUtf8, Utf8Error> {
         match str::from_utf8(c.to_bytes_with_nul()) {
             Ok(s) => Ok(unsafe { CUtf8::from_str_unchecked(s) }),
-            Err(e) => return Err(e),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the C string, panicking if it is not valid UTF-8.
+    ///
+    /// This is meant for converting `c"..."` literals and other `&CStr`
+    /// constants in `const` and `static` items, where the panic becomes a
+    /// compile-time error. Use [`from_c_str`](#method.from_c_str) to handle
+    /// invalid strings at runtime instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `c` is not encoded as UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```edition2021
+    /// use std::ffi::CStr;
+    /// use c_utf8::CUtf8;
+    ///
+    /// const GREETING: &CStr = c"Grüße";
+    /// const GREETING_UTF8: &CUtf8 = CUtf8::from_c_str_const(GREETING);
+    ///
+    /// static NAME: &CUtf8 = CUtf8::from_c_str_const(c"name");
+    ///
+    /// assert_eq!(GREETING_UTF8.as_str(), "Grüße");
+    /// assert_eq!(NAME.as_bytes_with_nul(), b"name\0");
+    /// ```
+    ///
+    /// Invalid UTF-8 fails to compile:
+    ///
+    /// ```compile_fail,edition2021
+    /// const BAD: &c_utf8::CUtf8 = c_utf8::CUtf8::from_c_str_const(c"\xFF");
+    /// ```
+    #[inline]
+    pub const fn from_c_str_const(c: &CStr) -> &CUtf8 {
+        match CUtf8::from_c_str(c) {
+            Ok(s) => s,
+            Err(_) => panic!("C string is not valid UTF-8"),
         }
     }
 
     /// Returns the raw C string if it is valid UTF-8 up to the first nul byte.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be non-null and point to a valid nul-terminated C string that
+    /// outlives `'a`, as required by
+    /// [`CStr::from_ptr`](https://doc.rust-lang.org/core/ffi/struct.CStr.html#method.from_ptr).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::ffi::c_char;
+    /// use c_utf8::CUtf8;
+    ///
+    /// let raw = b"from C\0".as_ptr() as *const c_char;
+    /// let s = unsafe { CUtf8::from_ptr(raw) }.unwrap();
+    /// assert_eq!(s.as_str(), "from C");
+    ///
+    /// let raw = b"\xFF\0".as_ptr() as *const c_char;
+    /// assert!(unsafe { CUtf8::from_ptr(raw) }.is_err());
+    /// ```
     #[inline]
     pub unsafe fn from_ptr<'a>(raw: *const c_char) -> Result<&'a CUtf8, Utf8Error> {
         CUtf8::from_c_str(CStr::from_ptr(raw))
@@ -207,35 +669,383 @@ impl CUtf8 {
         self.0.len() == 1
     }
 
+    /// Returns `true` if `prefix` is a prefix of `self`, disregarding the
+    /// trailing nul byte.
+    ///
+    /// Unlike [`str::starts_with`], this can be evaluated at compile time, such as
+    /// for static assertions on generated string constants.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// use c_utf8::CUtf8;
+    ///
+    /// const ROUTE: &CUtf8 = c_utf8!("/api/users");
+    /// const _: () = assert!(ROUTE.const_starts_with("/api/"));
+    ///
+    /// assert!(!ROUTE.const_starts_with("/users"));
+    /// # }
+    /// ```
+    ///
+    /// [`str::starts_with`]: https://doc.rust-lang.org/std/primitive.str.html#method.starts_with
+    pub const fn const_starts_with(&self, prefix: &str) -> bool {
+        let bytes = self.0.as_bytes();
+        let prefix = prefix.as_bytes();
+        // Exclude the trailing nul byte
+        if prefix.len() >= bytes.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < prefix.len() {
+            if bytes[i] != prefix[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns `true` if `suffix` is a suffix of `self`, disregarding the
+    /// trailing nul byte.
+    ///
+    /// Unlike [`str::ends_with`], this can be evaluated at compile time.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// use c_utf8::CUtf8;
+    ///
+    /// const LIB: &CUtf8 = c_utf8!("libfoo.so");
+    /// const _: () = assert!(LIB.const_ends_with(".so"));
+    ///
+    /// assert!(!LIB.const_ends_with(".so\0"));
+    /// # }
+    /// ```
+    ///
+    /// [`str::ends_with`]: https://doc.rust-lang.org/std/primitive.str.html#method.ends_with
+    pub const fn const_ends_with(&self, suffix: &str) -> bool {
+        let bytes = self.0.as_bytes();
+        let suffix = suffix.as_bytes();
+        // Exclude the trailing nul byte
+        if suffix.len() >= bytes.len() {
+            return false;
+        }
+        let start = bytes.len() - 1 - suffix.len();
+        let mut i = 0;
+        while i < suffix.len() {
+            if bytes[start + i] != suffix[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Divides `self` into a string slice and a C string at the byte offset
+    /// `mid`.
+    ///
+    /// Any suffix of a C string is also a C string, so the second half can be
+    /// passed to C without copying.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid` is greater than [`len`](#method.len) or does not lie
+    /// on a `char` boundary.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// let (ns, name) = c_utf8!("std::vector").split_at(5);
+    ///
+    /// assert_eq!(ns, "std::");
+    /// assert_eq!(name.as_bytes_with_nul(), b"vector\0");
+    /// # }
+    /// ```
+    #[inline]
+    pub fn split_at(&self, mid: usize) -> (&str, &CUtf8) {
+        assert!(mid <= self.len(), "byte index out of bounds");
+        let (head, tail) = self.0.split_at(mid);
+        (head, unsafe { CUtf8::from_str_unchecked(tail) })
+    }
+
+    /// Returns the rest of `self` after `prefix`, or `None` if `self` does not
+    /// start with `prefix`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// let url = c_utf8!("file:///tmp/log");
+    ///
+    /// let path = url.strip_prefix("file://").unwrap();
+    /// assert_eq!(path.as_bytes_with_nul(), b"/tmp/log\0");
+    ///
+    /// assert_eq!(url.strip_prefix("http://"), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn strip_prefix(&self, prefix: &str) -> Option<&CUtf8> {
+        if self.as_str().starts_with(prefix) {
+            Some(self.split_at(prefix.len()).1)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if all characters in `self` are within the ASCII range.
+    ///
+    /// This check is performed a word at a time, which makes it cheap enough to
+    /// branch on before calling into ASCII-only C APIs.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// assert!(c_utf8!("Hello!").is_ascii());
+    /// assert!(!c_utf8!("Héllo!").is_ascii());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_ascii(&self) -> bool {
+        self.as_bytes().is_ascii()
+    }
+
+    /// Returns the bytes of `self` without a trailing 0 byte if they are all
+    /// within the ASCII range, or `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// assert_eq!(c_utf8!("Hello!").as_ascii(), Some(&b"Hello!"[..]));
+    /// assert_eq!(c_utf8!("Héllo!").as_ascii(), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn as_ascii(&self) -> Option<&[u8]> {
+        if self.is_ascii() {
+            Some(self.as_bytes())
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the UTF-16 code units of `self`, including a
+    /// trailing nul code unit.
+    ///
+    /// Collecting this into a `Vec<u16>` gives a wide string (`LPCWSTR`) that
+    /// can be passed to Windows APIs.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// let wide: Vec<u16> = c_utf8!("Hi😀").encode_utf16_with_nul().collect();
+    ///
+    /// assert_eq!(wide, [0x48, 0x69, 0xD83D, 0xDE00, 0]);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn encode_utf16_with_nul(&self) -> EncodeUtf16 {
+        self.as_str_with_nul().encode_utf16()
+    }
+
+    /// Converts `self` to its ASCII lowercase equivalent in place.
+    ///
+    /// Non-ASCII characters and the trailing nul byte are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8;
+    ///
+    /// let mut buf = "Grüße, JÜRGEN\0".as_bytes().to_vec();
+    /// let s = CUtf8::from_bytes_mut(&mut buf).unwrap();
+    /// s.make_ascii_lowercase();
+    ///
+    /// assert_eq!(s.as_str(), "grüße, jÜrgen");
+    /// ```
+    #[inline]
+    pub fn make_ascii_lowercase(&mut self) {
+        self.0.make_ascii_lowercase();
+    }
+
+    /// Converts `self` to its ASCII uppercase equivalent in place.
+    ///
+    /// Non-ASCII characters and the trailing nul byte are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8;
+    ///
+    /// let mut buf = "Grüße, Jürgen\0".as_bytes().to_vec();
+    /// let s = CUtf8::from_bytes_mut(&mut buf).unwrap();
+    /// s.make_ascii_uppercase();
+    ///
+    /// assert_eq!(s.as_str(), "GRüßE, JüRGEN");
+    /// ```
+    #[inline]
+    pub fn make_ascii_uppercase(&mut self) {
+        self.0.make_ascii_uppercase();
+    }
+
+    /// Returns the byte offset of the first occurrence of `byte` in `self`,
+    /// disregarding the trailing nul byte.
+    ///
+    /// This is a fast path for finding ASCII delimiters in strings from C.
+    /// With the `memchr` feature, the search is vectorized via [`memchr`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// let var = c_utf8!("PATH=/usr/bin:/bin");
+    ///
+    /// assert_eq!(var.find_byte(b'='), Some(4));
+    /// assert_eq!(var.find_byte(b'!'), None);
+    /// assert_eq!(var.find_byte(0), None);
+    /// # }
+    /// ```
+    ///
+    /// [`memchr`]: https://docs.rs/memchr
+    #[inline]
+    pub fn find_byte(&self, byte: u8) -> Option<usize> {
+        #[cfg(feature = "memchr")]
+        {
+            memchr::memchr(byte, self.as_bytes())
+        }
+        #[cfg(not(feature = "memchr"))]
+        {
+            self.as_bytes().iter().position(|&b| b == byte)
+        }
+    }
+
+    /// Returns the byte offset of the last occurrence of `byte` in `self`,
+    /// disregarding the trailing nul byte.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// let path = c_utf8!("/usr/lib/libc.so");
+    ///
+    /// assert_eq!(path.rfind_byte(b'/'), Some(8));
+    /// assert_eq!(&path.as_str()[9..], "libc.so");
+    /// # }
+    /// ```
+    #[inline]
+    pub fn rfind_byte(&self, byte: u8) -> Option<usize> {
+        #[cfg(feature = "memchr")]
+        {
+            memchr::memrchr(byte, self.as_bytes())
+        }
+        #[cfg(not(feature = "memchr"))]
+        {
+            self.as_bytes().iter().rposition(|&b| b == byte)
+        }
+    }
+
     /// Returns a C string without checking UTF-8 validity or for a trailing
     /// nul byte.
+    ///
+    /// # Safety
+    ///
+    /// `b` must be valid UTF-8 and end with a nul byte.
     #[inline]
-    pub unsafe fn from_bytes_unchecked(b: &[u8]) -> &CUtf8 {
+    pub const unsafe fn from_bytes_unchecked(b: &[u8]) -> &CUtf8 {
         &*(b as *const [u8] as *const CUtf8)
     }
 
     /// Returns a C string without checking for a trailing nul byte.
+    ///
+    /// # Safety
+    ///
+    /// `s` must end with a nul byte.
     #[inline]
     pub const unsafe fn from_str_unchecked(s: &str) -> &CUtf8 {
         &*(s as *const str as *const CUtf8)
     }
 
     /// Returns a mutable C string without checking for a trailing nul byte.
+    ///
+    /// # Safety
+    ///
+    /// `s` must end with a nul byte.
     #[inline]
     pub unsafe fn from_str_unchecked_mut(s: &mut str) -> &mut CUtf8 {
         &mut *(s as *mut str as *mut CUtf8)
     }
 
     /// Returns a C string without checking UTF-8 validity.
+    ///
+    /// # Safety
+    ///
+    /// The bytes of `c` must be valid UTF-8.
     #[inline]
     pub unsafe fn from_c_str_unchecked(c: &CStr) -> &CUtf8 {
         Self::from_bytes_unchecked(c.to_bytes_with_nul())
     }
 
     /// Returns a pointer to the start of the raw C string.
+    ///
+    /// This is a `const fn`, which allows for building static tables of C
+    /// string pointers at compile time.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// use std::ffi::{c_char, CStr};
+    ///
+    /// #[repr(C)]
+    /// struct Command {
+    ///     name: *const c_char,
+    ///     id: u32,
+    /// }
+    ///
+    /// // Sound because the pointers are to immutable static data
+    /// unsafe impl Sync for Command {}
+    ///
+    /// static COMMANDS: [Command; 2] = [
+    ///     Command { name: c_utf8!("open").as_ptr(), id: 1 },
+    ///     Command { name: c_utf8!("close").as_ptr(), id: 2 },
+    /// ];
+    ///
+    /// let name = unsafe { CStr::from_ptr(COMMANDS[1].name) };
+    /// assert_eq!(name.to_str(), Ok("close"));
+    /// # }
+    /// ```
     #[inline]
-    pub fn as_ptr(&self) -> *const c_char {
-        self.as_bytes().as_ptr() as *const c_char
+    pub const fn as_ptr(&self) -> *const c_char {
+        self.0.as_ptr() as *const c_char
     }
 
     /// Returns `self` as a normal C string.
@@ -269,4 +1079,173 @@ impl CUtf8 {
     pub fn as_bytes_with_nul(&self) -> &[u8] {
         self.as_str_with_nul().as_bytes()
     }
+
+    /// Returns the bytes of `self` without a trailing 0 byte.
+    ///
+    /// This is an alias of [`as_bytes`](#method.as_bytes) that matches the
+    /// naming of [`CStr::to_bytes`].
+    ///
+    /// [`CStr::to_bytes`]: https://doc.rust-lang.org/core/ffi/struct.CStr.html#method.to_bytes
+    #[inline]
+    pub fn to_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    /// Returns the bytes of `self` with a trailing 0 byte.
+    ///
+    /// This is an alias of [`as_bytes_with_nul`](#method.as_bytes_with_nul)
+    /// that matches the naming of [`CStr::to_bytes_with_nul`].
+    ///
+    /// [`CStr::to_bytes_with_nul`]: https://doc.rust-lang.org/core/ffi/struct.CStr.html#method.to_bytes_with_nul
+    #[inline]
+    pub fn to_bytes_with_nul(&self) -> &[u8] {
+        self.as_bytes_with_nul()
+    }
+
+    /// Returns `self` as a normal UTF-8 encoded string.
+    ///
+    /// This is an alias of [`as_str`](#method.as_str) that matches the naming
+    /// of [`CStr::to_str`]. Unlike `CStr::to_str`, this cannot fail because
+    /// `self` is already known to be UTF-8.
+    ///
+    /// [`CStr::to_str`]: https://doc.rust-lang.org/core/ffi/struct.CStr.html#method.to_str
+    #[inline]
+    pub fn to_str(&self) -> &str {
+        self.as_str()
+    }
+
+    /// Copies `self` and its trailing nul byte into the start of `dst`,
+    /// returning the initialized copy.
+    ///
+    /// This allows for filling uninitialized buffers handed out by C APIs
+    /// without ever creating a `&mut [u8]` over uninitialized memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Capacity`] if `dst` is shorter than
+    /// [`as_bytes_with_nul`](#method.as_bytes_with_nul).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut buf = [MaybeUninit::<u8>::uninit(); 8];
+    /// let copy = c_utf8!("Hey").copy_to_uninit(&mut buf).unwrap();
+    ///
+    /// assert_eq!(copy.as_bytes_with_nul(), b"Hey\0");
+    ///
+    /// let mut small = [MaybeUninit::<u8>::uninit(); 3];
+    /// assert!(c_utf8!("Hey").copy_to_uninit(&mut small).is_err());
+    /// # }
+    /// ```
+    ///
+    /// [`Error::Capacity`]: enum.Error.html#variant.Capacity
+    pub fn copy_to_uninit<'a>(&self, dst: &'a mut [MaybeUninit<u8>]) -> Result<&'a CUtf8, Error> {
+        let src = self.as_bytes_with_nul();
+        let dst = match dst.get_mut(..src.len()) {
+            Some(dst) => dst,
+            None => return Err(Error::Capacity),
+        };
+        unsafe {
+            let dst = dst.as_mut_ptr() as *mut u8;
+            ptr::copy_nonoverlapping(src.as_ptr(), dst, src.len());
+            // `src` was already valid and is now fully copied into `dst`
+            Ok(CUtf8::from_bytes_unchecked(slice::from_raw_parts(
+                dst,
+                src.len(),
+            )))
+        }
+    }
+}
+
+/// Every byte of a word with its high bit set.
+const HI: u64 = u64::from_ne_bytes([0x80; 8]);
+
+/// Every byte of a word set to 1.
+const LO: u64 = u64::from_ne_bytes([0x01; 8]);
+
+/// Returns `true` if `word` contains no nul or non-ASCII bytes.
+#[inline]
+const fn is_nonzero_ascii(word: u64) -> bool {
+    (word & HI) | (word.wrapping_sub(LO) & !word & HI) == 0
+}
+
+/// Checks that `bytes` is UTF-8 with exactly one nul byte, at the end.
+const fn validate_with_nul(bytes: &[u8]) -> Result<(), Error> {
+    match bytes {
+        // The error is rebuilt from all of `bytes`, so that a truncated
+        // character before the nul byte is reported as invalid rather than
+        // incomplete
+        [s @ .., 0] => match validate_no_nul(s) {
+            Err(Error::Utf8(_)) => Err(utf8_error(bytes)),
+            result => result,
+        },
+        _ => Err(Error::MissingNul),
+    }
+}
+
+/// Checks that `bytes` is UTF-8 without any nul bytes.
+pub(crate) const fn validate_no_nul(bytes: &[u8]) -> Result<(), Error> {
+    let end = bytes.len();
+
+    let mut i = 0;
+    while i < end {
+        let first = bytes[i];
+
+        if first < 0x80 {
+            if first == 0 {
+                return Err(Error::InteriorNul { position: i });
+            }
+            i += 1;
+
+            // Skip over runs of ASCII without nul bytes a word at a time
+            while i + 8 <= end {
+                // SAFETY: `i + 8 <= end == bytes.len()`
+                let word = unsafe { ptr::read_unaligned(bytes.as_ptr().add(i) as *const u64) };
+                if !is_nonzero_ascii(word) {
+                    break;
+                }
+                i += 8;
+            }
+            continue;
+        }
+
+        // The valid ranges of the second byte, as in the Unicode standard's
+        // table of well-formed UTF-8 byte sequences
+        let (width, lo, hi) = match first {
+            0xC2..=0xDF => (2, 0x80, 0xBF),
+            0xE0 => (3, 0xA0, 0xBF),
+            0xE1..=0xEC | 0xEE..=0xEF => (3, 0x80, 0xBF),
+            0xED => (3, 0x80, 0x9F),
+            0xF0 => (4, 0x90, 0xBF),
+            0xF1..=0xF3 => (4, 0x80, 0xBF),
+            0xF4 => (4, 0x80, 0x8F),
+            _ => return Err(utf8_error(bytes)),
+        };
+        if i + width > end || bytes[i + 1] < lo || bytes[i + 1] > hi {
+            return Err(utf8_error(bytes));
+        }
+        let mut j = 2;
+        while j < width {
+            if bytes[i + j] & 0xC0 != 0x80 {
+                return Err(utf8_error(bytes));
+            }
+            j += 1;
+        }
+        i += width;
+    }
+    Ok(())
+}
+
+/// Returns the error for `bytes`, which are known not to be UTF-8.
+#[cold]
+const fn utf8_error(bytes: &[u8]) -> Error {
+    match str::from_utf8(bytes) {
+        Err(err) => Error::Utf8(err),
+        Ok(_) => unreachable!(),
+    }
 }
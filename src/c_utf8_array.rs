@@ -0,0 +1,236 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::c_utf8::CUtf8;
+use crate::error::Error;
+
+/// An owned UTF-8 encoded C string that is stored inline in a `[u8; N]`,
+/// without allocating.
+///
+/// This allows for building dynamic C strings on targets without `alloc`. The
+/// array includes the trailing nul byte, so the string itself can be at most
+/// `N - 1` bytes long. `N` must not be 0.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use std::fmt::Write;
+/// use c_utf8::CUtf8Array;
+///
+/// let mut name = CUtf8Array::<16>::new();
+/// write!(name, "sensor-{}", 42).unwrap();
+///
+/// assert_eq!(name.as_bytes_with_nul(), b"sensor-42\0");
+///
+/// assert!(name.push_str(" is too long").is_err());
+/// assert_eq!(name.as_str(), "sensor-42");
+/// ```
+///
+/// There must be room for at least the nul byte:
+///
+/// ```compile_fail
+/// let _fails = c_utf8::CUtf8Array::<0>::new();
+/// ```
+#[derive(Clone, Copy)]
+pub struct CUtf8Array<const N: usize> {
+    /// The length of the string, disregarding the trailing nul byte.
+    len: usize,
+    /// UTF-8 without nul bytes up to `len`, followed by a nul byte.
+    buf: [u8; N],
+}
+
+impl<const N: usize> CUtf8Array<N> {
+    const NON_EMPTY: () = assert!(N > 0, "CUtf8Array must have room for a nul byte");
+
+    /// Creates a new empty C string.
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    pub const fn new() -> CUtf8Array<N> {
+        let () = Self::NON_EMPTY;
+        CUtf8Array {
+            len: 0,
+            buf: [0; N],
+        }
+    }
+
+    /// Returns the maximum number of bytes the string can hold, disregarding
+    /// the trailing nul byte.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N - 1
+    }
+
+    /// Appends a given string slice onto the end of `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InteriorNul`] if `s` contains a nul byte, with its
+    /// position in the resulting string, or [`Error::Capacity`] if `s` does
+    /// not fit. Nothing is appended in either case.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::{CUtf8Array, Error};
+    ///
+    /// let mut s = CUtf8Array::<8>::new();
+    /// s.push_str("key").unwrap();
+    ///
+    /// assert_eq!(s.push_str("=a\0b"), Err(Error::InteriorNul { position: 5 }));
+    /// assert_eq!(s.push_str("=value"), Err(Error::Capacity));
+    /// assert_eq!(s.as_bytes_with_nul(), b"key\0");
+    /// ```
+    ///
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    /// [`Error::Capacity`]:    enum.Error.html#variant.Capacity
+    pub fn push_str(&mut self, s: &str) -> Result<(), Error> {
+        if let Some(nul) = s.bytes().position(|b| b == 0) {
+            return Err(Error::InteriorNul {
+                position: self.len + nul,
+            });
+        }
+        let end = self.len + s.len();
+        if end >= N {
+            return Err(Error::Capacity);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.buf[end] = 0;
+        self.len = end;
+        Ok(())
+    }
+
+    /// Appends the given `char` to the end of `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`push_str`](#method.push_str), so `'\0'`
+    /// is rejected with [`Error::InteriorNul`].
+    ///
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    #[inline]
+    pub fn push(&mut self, c: char) -> Result<(), Error> {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Removes the contents of `self`, leaving only the trailing nul byte.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buf[0] = 0;
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Deref for CUtf8Array<N> {
+    type Target = CUtf8;
+
+    #[inline]
+    fn deref(&self) -> &CUtf8 {
+        // SAFETY: `buf` holds UTF-8 up to `len`, followed by a nul byte
+        unsafe { CUtf8::from_bytes_unchecked(self.buf.get_unchecked(..=self.len)) }
+    }
+}
+
+impl<const N: usize> Default for CUtf8Array<N> {
+    #[inline]
+    fn default() -> CUtf8Array<N> {
+        CUtf8Array::new()
+    }
+}
+
+impl<'a, const N: usize> TryFrom<&'a str> for CUtf8Array<N> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(s: &str) -> Result<CUtf8Array<N>, Self::Error> {
+        let mut array = CUtf8Array::new();
+        array.push_str(s)?;
+        Ok(array)
+    }
+}
+
+impl<const N: usize> AsRef<CUtf8> for CUtf8Array<N> {
+    #[inline]
+    fn as_ref(&self) -> &CUtf8 {
+        self
+    }
+}
+
+impl<const N: usize> AsRef<str> for CUtf8Array<N> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> Borrow<CUtf8> for CUtf8Array<N> {
+    #[inline]
+    fn borrow(&self) -> &CUtf8 {
+        self
+    }
+}
+
+impl<const N: usize> PartialEq for CUtf8Array<N> {
+    #[inline]
+    fn eq(&self, other: &CUtf8Array<N>) -> bool {
+        **self == **other
+    }
+}
+
+impl<const N: usize> Eq for CUtf8Array<N> {}
+
+impl<const N: usize> PartialEq<CUtf8> for CUtf8Array<N> {
+    #[inline]
+    fn eq(&self, other: &CUtf8) -> bool {
+        **self == *other
+    }
+}
+
+impl<const N: usize> PartialOrd for CUtf8Array<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &CUtf8Array<N>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for CUtf8Array<N> {
+    #[inline]
+    fn cmp(&self, other: &CUtf8Array<N>) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<const N: usize> Hash for CUtf8Array<N> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<const N: usize> fmt::Debug for CUtf8Array<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<const N: usize> fmt::Display for CUtf8Array<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Write for CUtf8Array<N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
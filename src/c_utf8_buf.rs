@@ -1,13 +1,33 @@
-use alloc::borrow::ToOwned;
+use alloc::borrow::{Cow, ToOwned};
 use alloc::boxed::Box;
+use alloc::collections::TryReserveError;
+use alloc::ffi::CString;
+use alloc::rc::Rc;
 use alloc::string::String;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
 use alloc::{vec, vec::Vec};
 use core::borrow::{Borrow, BorrowMut};
+use core::convert::TryFrom;
+use core::ffi::{c_char, CStr};
 use core::fmt;
-use core::iter::FromIterator;
-use core::ops::{Deref, DerefMut};
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, FusedIterator};
+use core::ops::{Add, AddAssign, Bound, Deref, DerefMut, RangeBounds};
+#[cfg(feature = "pattern")]
+use core::str::pattern::{Pattern, StrSearcher};
+use core::{ptr, slice};
 
-use crate::c_utf8::CUtf8;
+#[cfg(feature = "std")]
+use std::ffi::OsStr;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::c_utf8::{validate_no_nul, CUtf8};
+use crate::error::{check_no_nul, Error, UnescapeError};
+use crate::nul_terminated::NulTerminated;
 
 /// An owned, mutable UTF-8 encoded C string (akin to [`String`] or
 /// [`PathBuf`]).
@@ -36,20 +56,27 @@ use crate::c_utf8::CUtf8;
 /// [`String`]:  https://doc.rust-lang.org/std/string/struct.String.html
 /// [`PathBuf`]: https://doc.rust-lang.org/std/path/struct.PathBuf.html
 /// [collect]:   https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.collect
-#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
 pub struct CUtf8Buf(String);
 
-impl PartialEq<CUtf8> for CUtf8Buf {
+impl Hash for CUtf8Buf {
     #[inline]
-    fn eq(&self, other: &CUtf8) -> bool {
-        (**self) == *other
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
     }
 }
 
-impl PartialEq<CUtf8Buf> for CUtf8 {
+impl Clone for CUtf8Buf {
+    #[inline]
+    fn clone(&self) -> CUtf8Buf {
+        CUtf8Buf(self.0.clone())
+    }
+
+    /// Clones `source` into `self`, reusing the existing allocation of `self`
+    /// when it has enough capacity.
     #[inline]
-    fn eq(&self, other: &CUtf8Buf) -> bool {
-        other.eq(self)
+    fn clone_from(&mut self, source: &CUtf8Buf) {
+        self.0.clone_from(&source.0);
     }
 }
 
@@ -86,6 +113,58 @@ where
     }
 }
 
+/// Appends every item of the iterator, moving the nul byte only once.
+///
+/// # Examples
+///
+/// ```
+/// use c_utf8::CUtf8Buf;
+///
+/// let mut buf = CUtf8Buf::from("a");
+/// buf.extend(['b', 'c']);
+/// buf.extend(vec!["d", "e"]);
+///
+/// assert_eq!(buf.as_bytes_with_nul(), b"abcde\0");
+/// ```
+impl<T> Extend<T> for CUtf8Buf
+where
+    String: Extend<T>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, it: I) {
+        self.with_string(|s| s.extend(it));
+    }
+}
+
+/// Appends a string slice, like with `String`.
+///
+/// # Examples
+///
+/// ```
+/// use c_utf8::CUtf8Buf;
+///
+/// let mut buf = CUtf8Buf::from("lib") + "foo";
+/// buf += ".so";
+///
+/// assert_eq!(buf.as_bytes_with_nul(), b"libfoo.so\0");
+/// ```
+impl<'a> Add<&'a str> for CUtf8Buf {
+    type Output = CUtf8Buf;
+
+    #[inline]
+    fn add(mut self, s: &str) -> CUtf8Buf {
+        self.push_str(s);
+        self
+    }
+}
+
+impl<'a> AddAssign<&'a str> for CUtf8Buf {
+    #[inline]
+    fn add_assign(&mut self, s: &str) {
+        self.push_str(s);
+    }
+}
+
 impl fmt::Debug for CUtf8Buf {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -96,7 +175,7 @@ impl fmt::Debug for CUtf8Buf {
 impl fmt::Display for CUtf8Buf {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        (**self).fmt(f)
+        f.pad(self.as_str())
     }
 }
 
@@ -119,6 +198,41 @@ impl fmt::Write for CUtf8Buf {
     }
 }
 
+#[cfg(feature = "pattern")]
+impl<'a, 'b> Pattern<'a> for &'b CUtf8Buf {
+    type Searcher = StrSearcher<'a, 'b>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &'a str) -> StrSearcher<'a, 'b> {
+        self.as_str().into_searcher(haystack)
+    }
+
+    #[inline]
+    fn is_contained_in(self, haystack: &'a str) -> bool {
+        self.as_str().is_contained_in(haystack)
+    }
+
+    #[inline]
+    fn is_prefix_of(self, haystack: &'a str) -> bool {
+        self.as_str().is_prefix_of(haystack)
+    }
+
+    #[inline]
+    fn is_suffix_of(self, haystack: &'a str) -> bool {
+        self.as_str().is_suffix_of(haystack)
+    }
+
+    #[inline]
+    fn strip_prefix_of(self, haystack: &'a str) -> Option<&'a str> {
+        self.as_str().strip_prefix_of(haystack)
+    }
+
+    #[inline]
+    fn strip_suffix_of(self, haystack: &'a str) -> Option<&'a str> {
+        self.as_str().strip_suffix_of(haystack)
+    }
+}
+
 impl Borrow<CUtf8> for CUtf8Buf {
     #[inline]
     fn borrow(&self) -> &CUtf8 {
@@ -133,6 +247,36 @@ impl BorrowMut<CUtf8> for CUtf8Buf {
     }
 }
 
+/// Allows for looking up `CUtf8Buf` keys in collections by `&CStr`, which eases
+/// migrating from `CString` keys.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::ffi::CStr;
+/// use c_utf8::CUtf8Buf;
+///
+/// let mut map = HashMap::new();
+/// map.insert(CUtf8Buf::from("key"), 42);
+///
+/// let key = CStr::from_bytes_with_nul(b"key\0").unwrap();
+/// assert_eq!(map.get(key), Some(&42));
+/// ```
+impl Borrow<CStr> for CUtf8Buf {
+    #[inline]
+    fn borrow(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl Borrow<CStr> for Box<CUtf8> {
+    #[inline]
+    fn borrow(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
 impl AsRef<CUtf8> for CUtf8Buf {
     #[inline]
     fn as_ref(&self) -> &CUtf8 {
@@ -140,6 +284,29 @@ impl AsRef<CUtf8> for CUtf8Buf {
     }
 }
 
+impl AsRef<CStr> for CUtf8Buf {
+    #[inline]
+    fn as_ref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<Path> for CUtf8Buf {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        self.as_str().as_ref()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<OsStr> for CUtf8Buf {
+    #[inline]
+    fn as_ref(&self) -> &OsStr {
+        self.as_str().as_ref()
+    }
+}
+
 impl AsMut<CUtf8> for CUtf8Buf {
     #[inline]
     fn as_mut(&mut self) -> &mut CUtf8 {
@@ -156,6 +323,311 @@ impl ToOwned for CUtf8 {
     }
 }
 
+impl CUtf8 {
+    /// Returns the lowercase equivalent of `self`, as defined by
+    /// [`str::to_lowercase`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// let s = c_utf8!("Hello, ΟΔΥΣΣΕΎΣ");
+    ///
+    /// assert_eq!(s.to_lowercase().as_bytes_with_nul(), "hello, οδυσσεύς\0".as_bytes());
+    /// # }
+    /// ```
+    ///
+    /// [`str::to_lowercase`]: https://doc.rust-lang.org/std/primitive.str.html#method.to_lowercase
+    #[inline]
+    pub fn to_lowercase(&self) -> CUtf8Buf {
+        let mut buf = CUtf8Buf::with_capacity(self.len());
+        self.to_lowercase_into(&mut buf);
+        buf
+    }
+
+    /// Returns the uppercase equivalent of `self`, as defined by
+    /// [`str::to_uppercase`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// assert_eq!(c_utf8!("tschüß").to_uppercase().as_str(), "TSCHÜSS");
+    /// # }
+    /// ```
+    ///
+    /// [`str::to_uppercase`]: https://doc.rust-lang.org/std/primitive.str.html#method.to_uppercase
+    #[inline]
+    pub fn to_uppercase(&self) -> CUtf8Buf {
+        let mut buf = CUtf8Buf::with_capacity(self.len());
+        self.to_uppercase_into(&mut buf);
+        buf
+    }
+
+    /// Writes the lowercase equivalent of `self` into `buf`, as defined by
+    /// [`str::to_lowercase`], clearing `buf` first.
+    ///
+    /// This reuses the allocation of `buf` instead of allocating a new string.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::from("leftover");
+    /// c_utf8!("ὈΔΥΣΣΕΎΣ").to_lowercase_into(&mut buf);
+    ///
+    /// assert_eq!(buf.as_str(), "ὀδυσσεύς");
+    /// # }
+    /// ```
+    ///
+    /// [`str::to_lowercase`]: https://doc.rust-lang.org/std/primitive.str.html#method.to_lowercase
+    pub fn to_lowercase_into(&self, buf: &mut CUtf8Buf) {
+        let s = self.as_str();
+        buf.with_string(|inner| {
+            inner.clear();
+            // Only 'Σ' is converted differently based on its context
+            if s.contains('Σ') {
+                inner.push_str(&s.to_lowercase());
+            } else {
+                inner.extend(s.chars().flat_map(char::to_lowercase));
+            }
+        });
+    }
+
+    /// Writes the uppercase equivalent of `self` into `buf`, as defined by
+    /// [`str::to_uppercase`], clearing `buf` first.
+    ///
+    /// This reuses the allocation of `buf` instead of allocating a new string.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::new();
+    /// c_utf8!("tschüß").to_uppercase_into(&mut buf);
+    ///
+    /// assert_eq!(buf.as_str(), "TSCHÜSS");
+    /// # }
+    /// ```
+    ///
+    /// [`str::to_uppercase`]: https://doc.rust-lang.org/std/primitive.str.html#method.to_uppercase
+    pub fn to_uppercase_into(&self, buf: &mut CUtf8Buf) {
+        let s = self.as_str();
+        buf.with_string(|inner| {
+            inner.clear();
+            inner.extend(s.chars().flat_map(char::to_uppercase));
+        });
+    }
+
+    /// Writes `self` into `buf` with ASCII letters mapped to their lowercase
+    /// equivalents, clearing `buf` first.
+    ///
+    /// Non-ASCII characters are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::new();
+    /// c_utf8!("Grüße, JÜRGEN").to_ascii_lowercase_into(&mut buf);
+    ///
+    /// assert_eq!(buf.as_str(), "grüße, jÜrgen");
+    /// # }
+    /// ```
+    pub fn to_ascii_lowercase_into(&self, buf: &mut CUtf8Buf) {
+        let s = self.as_str();
+        buf.with_string(|inner| {
+            inner.clear();
+            inner.push_str(s);
+            inner.make_ascii_lowercase();
+        });
+    }
+
+    /// Writes `self` into `buf` with ASCII letters mapped to their uppercase
+    /// equivalents, clearing `buf` first.
+    ///
+    /// Non-ASCII characters are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::new();
+    /// c_utf8!("Grüße, Jürgen").to_ascii_uppercase_into(&mut buf);
+    ///
+    /// assert_eq!(buf.as_str(), "GRüßE, JüRGEN");
+    /// # }
+    /// ```
+    pub fn to_ascii_uppercase_into(&self, buf: &mut CUtf8Buf) {
+        let s = self.as_str();
+        buf.with_string(|inner| {
+            inner.clear();
+            inner.push_str(s);
+            inner.make_ascii_uppercase();
+        });
+    }
+
+    /// Returns a copy of `self` with every non-ASCII character escaped as
+    /// `\u{NNNN}` and every backslash escaped as `\\`.
+    ///
+    /// The result is pure ASCII, which makes it safe to pass to C sinks that
+    /// mangle UTF-8. It can be turned back into the original string with
+    /// [`CUtf8Buf::from_escaped`](struct.CUtf8Buf.html#method.from_escaped).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let s = c_utf8!("Grüße\\😀");
+    /// let escaped = s.escape_non_ascii();
+    ///
+    /// assert_eq!(escaped.as_str(), r"Gr\u{fc}\u{df}e\\\u{1f600}");
+    /// assert_eq!(CUtf8Buf::from_escaped(escaped.as_str()).unwrap(), *s);
+    /// # }
+    /// ```
+    pub fn escape_non_ascii(&self) -> CUtf8Buf {
+        let s = self.as_str();
+        let mut out = String::with_capacity(s.len() + 1);
+        for c in s.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                c if c.is_ascii() => out.push(c),
+                c => out.extend(c.escape_unicode()),
+            }
+        }
+        CUtf8Buf::from_string(out)
+    }
+
+    /// Returns `self` with all Unicode bidirectional control characters
+    /// removed.
+    ///
+    /// These characters can reorder how text is displayed, which makes
+    /// untrusted strings in logs and terminal output misleading (see
+    /// [Trojan Source]). The removed characters are U+061C, U+200E, U+200F,
+    /// U+202A through U+202E, and U+2066 through U+2069.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// let s = c_utf8!("access level: \u{202E}resu\u{202C}");
+    /// assert_eq!(s.strip_bidi_controls().as_str(), "access level: resu");
+    ///
+    /// let s = c_utf8!("plain");
+    /// assert_eq!(s.strip_bidi_controls().as_str(), "plain");
+    /// # }
+    /// ```
+    ///
+    /// [Trojan Source]: https://trojansource.codes
+    #[cfg(feature = "bidi")]
+    pub fn strip_bidi_controls(&self) -> Cow<CUtf8> {
+        let s = self.as_str();
+        if !s.contains(is_bidi_control) {
+            return Cow::Borrowed(self);
+        }
+        Cow::Owned(s.chars().filter(|&c| !is_bidi_control(c)).collect())
+    }
+
+    /// Returns `self` with all Unicode bidirectional control characters
+    /// replaced by `\u{...}` escapes, making them visible.
+    ///
+    /// This escapes the same characters that
+    /// [`strip_bidi_controls`](#method.strip_bidi_controls) removes.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// let s = c_utf8!("access level: \u{202E}resu\u{202C}");
+    ///
+    /// assert_eq!(
+    ///     s.escape_bidi_controls().as_str(),
+    ///     r"access level: \u{202e}resu\u{202c}",
+    /// );
+    /// # }
+    /// ```
+    #[cfg(feature = "bidi")]
+    pub fn escape_bidi_controls(&self) -> Cow<CUtf8> {
+        let s = self.as_str();
+        if !s.contains(is_bidi_control) {
+            return Cow::Borrowed(self);
+        }
+        let mut out = String::with_capacity(s.len() + 1);
+        for c in s.chars() {
+            if is_bidi_control(c) {
+                out.extend(c.escape_unicode());
+            } else {
+                out.push(c);
+            }
+        }
+        Cow::Owned(CUtf8Buf::from_string(out))
+    }
+
+    /// Returns an ASCII-only approximation of `self`, transliterating each
+    /// non-ASCII character with [`deunicode`].
+    ///
+    /// This is intended for C systems that cannot accept non-ASCII at all, such
+    /// as filenames on constrained devices or old terminal protocols. Characters
+    /// with no known transliteration become `[?]`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// let s = c_utf8!("Æneid, 北亰");
+    ///
+    /// assert_eq!(s.to_ascii_lossy_translit().as_str(), "AEneid, Bei Jing");
+    /// # }
+    /// ```
+    ///
+    /// [`deunicode`]: https://docs.rs/deunicode
+    #[cfg(feature = "deunicode")]
+    pub fn to_ascii_lossy_translit(&self) -> CUtf8Buf {
+        CUtf8Buf::from_string(deunicode::deunicode(self.as_str()))
+    }
+
+    /// Returns `self` as a borrowed UTF-8 encoded string.
+    ///
+    /// This matches the naming of [`CStr::to_string_lossy`], but never needs
+    /// to replace any data because `self` is already known to be UTF-8.
+    ///
+    /// [`CStr::to_string_lossy`]: https://doc.rust-lang.org/std/ffi/struct.CStr.html#method.to_string_lossy
+    #[inline]
+    pub fn to_string_lossy(&self) -> Cow<str> {
+        Cow::Borrowed(self.as_str())
+    }
+}
+
 impl<'a> From<&'a CUtf8> for CUtf8Buf {
     #[inline]
     fn from(c: &CUtf8) -> CUtf8Buf {
@@ -207,6 +679,54 @@ impl From<CUtf8Buf> for Box<CUtf8> {
     }
 }
 
+#[cfg(target_has_atomic = "ptr")]
+impl<'a> From<&'a CUtf8> for Arc<CUtf8> {
+    #[inline]
+    fn from(c: &CUtf8) -> Arc<CUtf8> {
+        let arc: Arc<str> = c.as_str_with_nul().into();
+        unsafe { Arc::from_raw(Arc::into_raw(arc) as *const CUtf8) }
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl From<CUtf8Buf> for Arc<CUtf8> {
+    #[inline]
+    fn from(buf: CUtf8Buf) -> Arc<CUtf8> {
+        let arc: Arc<str> = buf.0.into();
+        unsafe { Arc::from_raw(Arc::into_raw(arc) as *const CUtf8) }
+    }
+}
+
+impl<'a> From<&'a CUtf8> for Rc<CUtf8> {
+    #[inline]
+    fn from(c: &CUtf8) -> Rc<CUtf8> {
+        let rc: Rc<str> = c.as_str_with_nul().into();
+        unsafe { Rc::from_raw(Rc::into_raw(rc) as *const CUtf8) }
+    }
+}
+
+impl From<CUtf8Buf> for Rc<CUtf8> {
+    #[inline]
+    fn from(buf: CUtf8Buf) -> Rc<CUtf8> {
+        let rc: Rc<str> = buf.0.into();
+        unsafe { Rc::from_raw(Rc::into_raw(rc) as *const CUtf8) }
+    }
+}
+
+impl<'a> From<&'a CUtf8> for String {
+    #[inline]
+    fn from(c: &CUtf8) -> String {
+        c.as_str().into()
+    }
+}
+
+impl<'a> From<&'a CUtf8> for Cow<'a, str> {
+    #[inline]
+    fn from(c: &'a CUtf8) -> Cow<'a, str> {
+        Cow::Borrowed(c.as_str())
+    }
+}
+
 impl From<CUtf8Buf> for String {
     #[inline]
     fn from(buf: CUtf8Buf) -> String {
@@ -214,6 +734,87 @@ impl From<CUtf8Buf> for String {
     }
 }
 
+/// Converts the buffer into a `CString` without copying.
+///
+/// # Errors
+///
+/// Returns [`Error::InteriorNul`](enum.Error.html#variant.InteriorNul) if the
+/// string contains a nul byte before its end, which C would read as the end of
+/// the string.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use std::ffi::CString;
+/// use c_utf8::{CUtf8Buf, Error};
+///
+/// let c = CString::try_from(CUtf8Buf::from("Hey")).unwrap();
+/// assert_eq!(c.as_bytes_with_nul(), b"Hey\0");
+///
+/// let err = CString::try_from(CUtf8Buf::from("a\0b")).unwrap_err();
+/// assert_eq!(err, Error::InteriorNul { position: 1 });
+/// ```
+impl TryFrom<CUtf8Buf> for CString {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(buf: CUtf8Buf) -> Result<CString, Self::Error> {
+        check_no_nul(buf.as_bytes())?;
+        // SAFETY: the only nul byte is the trailing one
+        Ok(unsafe { CString::from_vec_with_nul_unchecked(buf.into_bytes_with_nul()) })
+    }
+}
+
+impl TryFrom<CString> for CUtf8Buf {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(c: CString) -> Result<CUtf8Buf, Self::Error> {
+        CUtf8Buf::from_c_string(c)
+    }
+}
+
+impl TryFrom<Vec<u8>> for CUtf8Buf {
+    type Error = (Error, Vec<u8>);
+
+    #[inline]
+    fn try_from(bytes: Vec<u8>) -> Result<CUtf8Buf, Self::Error> {
+        CUtf8Buf::from_vec(bytes)
+    }
+}
+
+/// Reuses the allocation of the string, which is only grown if a nul
+/// terminator needs to be appended.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use c_utf8::CUtf8Buf;
+///
+/// let boxed: Box<str> = "key\0".into();
+/// let ptr = boxed.as_ptr();
+///
+/// let buf = CUtf8Buf::try_from(boxed).unwrap();
+/// assert_eq!(buf.as_str().as_ptr(), ptr);
+/// assert_eq!(buf.as_str(), "key");
+///
+/// let (err, boxed) = CUtf8Buf::try_from(Box::<str>::from("k\0v")).unwrap_err();
+/// assert_eq!(err.position(), Some(1));
+/// assert_eq!(&*boxed, "k\0v");
+/// ```
+impl TryFrom<Box<str>> for CUtf8Buf {
+    type Error = (Error, Box<str>);
+
+    fn try_from(s: Box<str>) -> Result<CUtf8Buf, Self::Error> {
+        match check_no_nul(without_nul(s.as_bytes())) {
+            Ok(()) => Ok(CUtf8Buf::from_string(s.into_string())),
+            Err(err) => Err((err, s)),
+        }
+    }
+}
+
 impl From<CUtf8Buf> for Vec<u8> {
     #[inline]
     fn from(buf: CUtf8Buf) -> Vec<u8> {
@@ -228,18 +829,565 @@ impl CUtf8Buf {
         CUtf8Buf(unsafe { String::from_utf8_unchecked(vec![0; 1]) })
     }
 
+    /// Creates a new empty `CUtf8Buf` with room for at least `capacity` bytes,
+    /// in addition to the trailing nul byte.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> CUtf8Buf {
+        let mut s = String::with_capacity(capacity.saturating_add(1));
+        s.push('\0');
+        CUtf8Buf(s)
+    }
+
     /// Creates a new C string from a UTF-8 string, appending a nul
     /// terminator if one doesn't already exist.
     #[inline]
     pub fn from_string(mut s: String) -> CUtf8Buf {
-        if s.as_bytes().last() != Some(&b'0') {
+        if !s.is_nul_terminated() {
             unsafe { s.as_mut_vec().push(0) };
         }
         CUtf8Buf(s)
     }
 
+    /// Creates a new C string from a `CString` without copying, if it is valid
+    /// UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Utf8`](enum.Error.html#variant.Utf8) if the bytes of
+    /// `c` are not encoded as UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::ffi::CString;
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let c = CString::new("Grüße").unwrap();
+    /// assert_eq!(CUtf8Buf::from_c_string(c).unwrap().as_str(), "Grüße");
+    ///
+    /// let c = CString::new(&b"\xFF"[..]).unwrap();
+    /// assert!(CUtf8Buf::from_c_string(c).is_err());
+    /// ```
+    #[inline]
+    pub fn from_c_string(c: CString) -> Result<CUtf8Buf, Error> {
+        match String::from_utf8(c.into_bytes_with_nul()) {
+            Ok(s) => Ok(CUtf8Buf(s)),
+            Err(err) => Err(Error::Utf8(err.utf8_error())),
+        }
+    }
+
+    /// Creates a new C string from UTF-8 bytes, reusing their allocation.
+    ///
+    /// The bytes may end with a nul terminator, in which case they are used
+    /// as is. Otherwise, one is appended, which only reallocates if `bytes` is
+    /// at capacity.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Utf8`] if `bytes` are not encoded as UTF-8, or
+    /// [`Error::InteriorNul`] if there is a nul byte before the end. The
+    /// original bytes are returned along with the error.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::{CUtf8Buf, Error};
+    ///
+    /// let mut bytes = Vec::with_capacity(16);
+    /// bytes.extend_from_slice(b"record");
+    /// let ptr = bytes.as_ptr();
+    ///
+    /// let buf = CUtf8Buf::from_vec(bytes).unwrap();
+    /// assert_eq!(buf.as_bytes_with_nul(), b"record\0");
+    /// assert_eq!(buf.as_str().as_ptr(), ptr);
+    ///
+    /// let (err, bytes) = CUtf8Buf::from_vec(b"bad \xFF".to_vec()).unwrap_err();
+    /// assert!(matches!(err, Error::Utf8(_)));
+    /// assert_eq!(bytes, b"bad \xFF");
+    /// ```
+    ///
+    /// [`Error::Utf8`]:        enum.Error.html#variant.Utf8
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    pub fn from_vec(bytes: Vec<u8>) -> Result<CUtf8Buf, (Error, Vec<u8>)> {
+        match validate_no_nul(without_nul(&bytes)) {
+            // SAFETY: the bytes were just checked to be UTF-8
+            Ok(()) => Ok(CUtf8Buf::from_string(unsafe {
+                String::from_utf8_unchecked(bytes)
+            })),
+            Err(err) => Err((err, bytes)),
+        }
+    }
+
+    /// Creates a new C string from a path, if it is valid UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Utf8`] if `path` is not valid UTF-8, or
+    /// [`Error::InteriorNul`] if it contains a nul byte.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let path = CUtf8Buf::from_path(Path::new("/etc/hosts")).unwrap();
+    /// assert_eq!(path.as_bytes_with_nul(), b"/etc/hosts\0");
+    /// ```
+    ///
+    /// [`Error::Utf8`]:        enum.Error.html#variant.Utf8
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_path(path: &Path) -> Result<CUtf8Buf, Error> {
+        CUtf8Buf::from_os_str(path.as_os_str())
+    }
+
+    /// Creates a new C string from an OS string, if it is valid UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Utf8`] if `s` is not valid UTF-8, or
+    /// [`Error::InteriorNul`] if it contains a nul byte.
+    ///
+    /// [`Error::Utf8`]:        enum.Error.html#variant.Utf8
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    #[cfg(feature = "std")]
+    pub fn from_os_str(s: &OsStr) -> Result<CUtf8Buf, Error> {
+        let s = core::str::from_utf8(s.as_encoded_bytes())?;
+        check_no_nul(s.as_bytes())?;
+        Ok(CUtf8Buf::from(s))
+    }
+
+    /// Reads bytes from `reader` up to and including the next nul byte, and
+    /// returns them as a C string.
+    ///
+    /// This is the inverse of writing [`as_bytes_with_nul`] to a stream, and
+    /// can be used to parse nul-delimited records such as the output of
+    /// `git ls-files -z`.
+    ///
+    /// # Errors
+    ///
+    /// Any error from `reader` is returned as is. If the stream ends before a
+    /// nul byte is found, an error of kind [`UnexpectedEof`] is returned, and
+    /// if the bytes read are not valid UTF-8, an error of kind
+    /// [`InvalidData`] is returned. In both cases the bytes read so far are
+    /// consumed, and the [`Error`] can be recovered with
+    /// [`io::Error::into_inner`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::io::{self, Cursor};
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut reader = Cursor::new(b"src/lib.rs\0README.md\0");
+    ///
+    /// let first = CUtf8Buf::read_from(&mut reader).unwrap();
+    /// assert_eq!(first.as_bytes_with_nul(), b"src/lib.rs\0");
+    ///
+    /// let second = CUtf8Buf::read_from(&mut reader).unwrap();
+    /// assert_eq!(second.as_str(), "README.md");
+    ///
+    /// let err = CUtf8Buf::read_from(&mut reader).unwrap_err();
+    /// assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    /// ```
+    ///
+    /// [`as_bytes_with_nul`]:     struct.CUtf8.html#method.as_bytes_with_nul
+    /// [`Error`]:                 enum.Error.html
+    /// [`UnexpectedEof`]:         https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof
+    /// [`InvalidData`]:           https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+    /// [`io::Error::into_inner`]: https://doc.rust-lang.org/std/io/struct.Error.html#method.into_inner
+    #[cfg(feature = "std")]
+    pub fn read_from<R: BufRead + ?Sized>(reader: &mut R) -> io::Result<CUtf8Buf> {
+        let mut bytes = Vec::new();
+        reader.read_until(0, &mut bytes)?;
+        if bytes.last() != Some(&0) {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                Error::MissingNul,
+            ));
+        }
+        match String::from_utf8(bytes) {
+            // `read_until` stops at the first nul byte
+            Ok(s) => Ok(CUtf8Buf(s)),
+            Err(err) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                Error::Utf8(err.utf8_error()),
+            )),
+        }
+    }
+
+    /// Returns an iterator over the nul-terminated strings read from `reader`,
+    /// as by repeatedly calling [`read_from`](#method.read_from).
+    ///
+    /// The iterator ends once the stream is exhausted. If there are bytes left
+    /// without a trailing nul byte, an error of kind [`UnexpectedEof`] is
+    /// yielded before it ends.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let reader = Cursor::new(b"a.txt\0b.txt\0");
+    /// let files = CUtf8Buf::records(reader)
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(files, ["a.txt", "b.txt"]);
+    ///
+    /// let mut records = CUtf8Buf::records(Cursor::new(b"ok\0bad \xFF\0unterminated"));
+    /// assert_eq!(records.next().unwrap().unwrap(), "ok");
+    /// assert!(records.next().unwrap().is_err());
+    /// assert!(records.next().unwrap().is_err());
+    /// assert!(records.next().is_none());
+    /// ```
+    ///
+    /// [`UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn records<R: BufRead>(reader: R) -> Records<R> {
+        Records { reader }
+    }
+
+    /// Creates a C string from arbitrary bytes, such as those received from C,
+    /// replacing invalid UTF-8 with
+    /// [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+    ///
+    /// Like a C API would, this stops at the first nul byte. If there is none,
+    /// one is appended. The input is borrowed if it needs neither fix.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let s = CUtf8Buf::from_bytes_lossy(b"ok\0garbage\xFF");
+    /// assert!(matches!(s, Cow::Borrowed(_)));
+    /// assert_eq!(s.as_str(), "ok");
+    ///
+    /// let s = CUtf8Buf::from_bytes_lossy(b"bad \xFF byte");
+    /// assert!(matches!(s, Cow::Owned(_)));
+    /// assert_eq!(s.as_str(), "bad \u{FFFD} byte");
+    /// ```
+    ///
+    /// [U+FFFD]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
+    pub fn from_bytes_lossy(bytes: &[u8]) -> Cow<CUtf8> {
+        if let Ok((s, _)) = CUtf8::from_bytes_until_nul(bytes) {
+            return Cow::Borrowed(s);
+        }
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        let s = String::from_utf8_lossy(&bytes[..end]).into_owned();
+        Cow::Owned(CUtf8Buf::from_string(s))
+    }
+
+    /// Creates a new C string by parsing the escape sequences in `s`.
+    ///
+    /// The following escapes are supported:
+    ///
+    /// - `\n`, `\r`, `\t`, `\\`, `\'`, and `\"`
+    /// - `\xNN`, where `NN` is an ASCII character code in hexadecimal
+    /// - `\u{N...}`, where `N...` is 1 to 6 hexadecimal digits of a Unicode
+    ///   scalar value
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an escape sequence is malformed or if the result
+    /// would contain a nul byte, such as from `\0` or `\x00`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::{CUtf8Buf, UnescapeError};
+    ///
+    /// let s = CUtf8Buf::from_escaped(r"tab:\t\x41\u{e9}\\").unwrap();
+    /// assert_eq!(s.as_str(), "tab:\tAé\\");
+    ///
+    /// assert_eq!(CUtf8Buf::from_escaped(r"\x00"), Err(UnescapeError::Nul));
+    /// assert_eq!(CUtf8Buf::from_escaped(r"\q"), Err(UnescapeError::UnknownEscape('q')));
+    /// ```
+    pub fn from_escaped(s: &str) -> Result<CUtf8Buf, UnescapeError> {
+        if s.as_bytes().contains(&0) {
+            return Err(UnescapeError::Nul);
+        }
+
+        let mut out = String::with_capacity(s.len() + 1);
+        let mut chars = s.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            let c = match chars.next() {
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some(c @ '\\') | Some(c @ '\'') | Some(c @ '"') => c,
+                Some('0') => return Err(UnescapeError::Nul),
+                Some('x') => {
+                    let code = chars
+                        .as_str()
+                        .get(..2)
+                        .filter(|d| d.bytes().all(|b| b.is_ascii_hexdigit()))
+                        .and_then(|d| u8::from_str_radix(d, 16).ok());
+                    match code {
+                        Some(0) => return Err(UnescapeError::Nul),
+                        Some(code) if code.is_ascii() => {
+                            chars.nth(1);
+                            code as char
+                        }
+                        _ => return Err(UnescapeError::InvalidHexEscape),
+                    }
+                }
+                Some('u') => {
+                    let rest = chars.as_str();
+                    let end = match rest.find('}') {
+                        Some(end) if rest.starts_with('{') && (2..=7).contains(&end) => end,
+                        _ => return Err(UnescapeError::InvalidUnicodeEscape),
+                    };
+                    let digits = &rest[1..end];
+                    let code = Some(digits)
+                        .filter(|d| d.bytes().all(|b| b.is_ascii_hexdigit()))
+                        .and_then(|d| u32::from_str_radix(d, 16).ok());
+                    match code.and_then(char::from_u32) {
+                        Some('\0') => return Err(UnescapeError::Nul),
+                        Some(c) => {
+                            chars.nth(end);
+                            c
+                        }
+                        None => return Err(UnescapeError::InvalidUnicodeEscape),
+                    }
+                }
+                Some(c) => return Err(UnescapeError::UnknownEscape(c)),
+                None => return Err(UnescapeError::TrailingBackslash),
+            };
+            out.push(c);
+        }
+
+        Ok(CUtf8Buf::from_string(out))
+    }
+
+    /// Creates a new C string from UTF-16 encoded `units`, such as a wide
+    /// string from a Win32 API.
+    ///
+    /// A single trailing nul code unit is allowed and ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Utf16`] if `units` contains unpaired surrogates, and
+    /// [`Error::InteriorNul`] if it contains a nul code unit before the end.
+    /// Positions are in bytes, which is twice the index in `units`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let wide: Vec<u16> = "Hi😀\0".encode_utf16().collect();
+    /// let s = CUtf8Buf::from_utf16(&wide).unwrap();
+    ///
+    /// assert_eq!(s.as_str(), "Hi😀");
+    /// assert!(CUtf8Buf::from_utf16(&[0xD800]).is_err());
+    /// ```
+    ///
+    /// [`Error::Utf16`]:       enum.Error.html#variant.Utf16
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    #[inline]
+    pub fn from_utf16(units: &[u16]) -> Result<CUtf8Buf, Error> {
+        decode_utf16(units.iter().cloned())
+    }
+
+    /// Creates a new C string from UTF-16 encoded `units`, replacing unpaired
+    /// surrogates with [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+    ///
+    /// Like a C API would, this stops at the first nul code unit.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let s = CUtf8Buf::from_utf16_lossy(&[0x48, 0xD800, 0x69, 0, 0x21]);
+    /// assert_eq!(s.as_str(), "H\u{FFFD}i");
+    /// ```
+    ///
+    /// [U+FFFD]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
+    #[inline]
+    pub fn from_utf16_lossy(units: &[u16]) -> CUtf8Buf {
+        decode_utf16_lossy(units.iter().cloned(), false)
+    }
+
+    /// Creates a new C string from little-endian UTF-16 encoded `bytes`, such
+    /// as those read from Windows files or USB string descriptors.
+    ///
+    /// `bytes` does not need to be aligned to `u16`. A single trailing nul code
+    /// unit is allowed and ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Utf16`] if `bytes` has an odd length or contains
+    /// unpaired surrogates, and [`Error::InteriorNul`] if it contains a nul code unit
+    /// before the end.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let bytes = [0x48, 0x00, 0x69, 0x00, 0x3D, 0xD8, 0x00, 0xDE, 0x00, 0x00];
+    /// let s = CUtf8Buf::from_utf16le_bytes(&bytes).unwrap();
+    ///
+    /// assert_eq!(s.as_str(), "Hi😀");
+    /// assert!(CUtf8Buf::from_utf16le_bytes(&bytes[..3]).is_err());
+    /// ```
+    ///
+    /// [`Error::Utf16`]: enum.Error.html#variant.Utf16
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    pub fn from_utf16le_bytes(bytes: &[u8]) -> Result<CUtf8Buf, Error> {
+        if bytes.len() % 2 != 0 {
+            return Err(Error::Utf16);
+        }
+        decode_utf16(utf16_units(bytes, u16::from_le_bytes))
+    }
+
+    /// Creates a new C string from big-endian UTF-16 encoded `bytes`.
+    ///
+    /// This behaves like [`from_utf16le_bytes`](#method.from_utf16le_bytes),
+    /// except for the byte order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let s = CUtf8Buf::from_utf16be_bytes(&[0x00, 0x48, 0x00, 0x69]).unwrap();
+    /// assert_eq!(s.as_str(), "Hi");
+    /// ```
+    pub fn from_utf16be_bytes(bytes: &[u8]) -> Result<CUtf8Buf, Error> {
+        if bytes.len() % 2 != 0 {
+            return Err(Error::Utf16);
+        }
+        decode_utf16(utf16_units(bytes, u16::from_be_bytes))
+    }
+
+    /// Creates a new C string from little-endian UTF-16 encoded `bytes`,
+    /// replacing invalid data with
+    /// [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+    ///
+    /// Like a C API would, this stops at the first nul code unit. A trailing
+    /// odd byte is treated as invalid data.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let bytes = [0x48, 0x00, 0x00, 0xD8, 0x69, 0x00, 0x00, 0x00, 0x21, 0x00];
+    /// let s = CUtf8Buf::from_utf16le_bytes_lossy(&bytes);
+    ///
+    /// assert_eq!(s.as_str(), "H\u{FFFD}i");
+    /// ```
+    ///
+    /// [U+FFFD]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
+    pub fn from_utf16le_bytes_lossy(bytes: &[u8]) -> CUtf8Buf {
+        let odd = bytes.len() % 2 != 0;
+        decode_utf16_lossy(utf16_units(bytes, u16::from_le_bytes), odd)
+    }
+
+    /// Creates a new C string from big-endian UTF-16 encoded `bytes`,
+    /// replacing invalid data with
+    /// [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+    ///
+    /// This behaves like
+    /// [`from_utf16le_bytes_lossy`](#method.from_utf16le_bytes_lossy), except
+    /// for the byte order.
+    ///
+    /// [U+FFFD]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
+    pub fn from_utf16be_bytes_lossy(bytes: &[u8]) -> CUtf8Buf {
+        let odd = bytes.len() % 2 != 0;
+        decode_utf16_lossy(utf16_units(bytes, u16::from_be_bytes), odd)
+    }
+
+    /// Creates a new C string from a nul-terminated wide string (`LPCWSTR`),
+    /// such as one returned by Windows APIs.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null, properly aligned, and point to a sequence of
+    /// `u16` code units terminated by a 0 code unit, all of which is valid for
+    /// reads.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Utf16`] if the wide string contains unpaired
+    /// surrogates.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let wide: Vec<u16> = "Hi😀\0".encode_utf16().collect();
+    /// let s = unsafe { CUtf8Buf::from_wide_ptr(wide.as_ptr()) }.unwrap();
+    ///
+    /// assert_eq!(s.as_str(), "Hi😀");
+    /// ```
+    ///
+    /// [`Error::Utf16`]: enum.Error.html#variant.Utf16
+    pub unsafe fn from_wide_ptr(ptr: *const u16) -> Result<CUtf8Buf, Error> {
+        decode_utf16(wide_units(ptr).iter().cloned())
+    }
+
+    /// Creates a new C string from a nul-terminated wide string (`LPCWSTR`),
+    /// replacing unpaired surrogates with
+    /// [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+    ///
+    /// # Safety
+    ///
+    /// This has the same requirements as
+    /// [`from_wide_ptr`](#method.from_wide_ptr).
+    ///
+    /// [U+FFFD]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
+    pub unsafe fn from_wide_ptr_lossy(ptr: *const u16) -> CUtf8Buf {
+        decode_utf16_lossy(wide_units(ptr).iter().cloned(), false)
+    }
+
     /// Creates a new C string from a native Rust string without checking for a
     /// nul terminator.
+    ///
+    /// # Safety
+    ///
+    /// `s` must end with a nul byte.
     #[inline]
     pub const unsafe fn from_string_unchecked(s: String) -> CUtf8Buf {
         CUtf8Buf(s)
@@ -250,27 +1398,420 @@ impl CUtf8Buf {
     where
         F: FnOnce(&mut String) -> T,
     {
+        /// Appends the nul byte when dropped, even if `f` panics.
+        struct Guard<'a>(&'a mut String);
+
+        impl<'a> Drop for Guard<'a> {
+            #[inline]
+            fn drop(&mut self) {
+                unsafe { self.0.as_mut_vec().push(0) };
+            }
+        }
+
         // Remove nul byte
         unsafe { self.0.as_mut_vec().pop() };
 
-        let val = f(&mut self.0);
+        let guard = Guard(&mut self.0);
+        f(guard.0)
+    }
 
-        // Append nul byte
-        unsafe { self.0.as_mut_vec().push(0) };
+    /// Appends `bytes` in place of the nul byte and moves it to the new end.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be valid UTF-8.
+    #[inline]
+    unsafe fn push_bytes(&mut self, bytes: &[u8]) {
+        let vec = self.0.as_mut_vec();
+        vec.reserve(bytes.len());
 
-        val
+        let nul = vec.len() - 1;
+        let dst = vec.as_mut_ptr().add(nul);
+        ptr::copy_nonoverlapping(bytes.as_ptr(), dst, bytes.len());
+        *dst.add(bytes.len()) = 0;
+        vec.set_len(nul + bytes.len() + 1);
     }
 
     /// Appends a given string slice onto the end of this `CUtf8Buf`.
     #[inline]
     pub fn push_str(&mut self, s: &str) {
-        self.with_string(|inner| inner.push_str(s));
+        unsafe { self.push_bytes(s.as_bytes()) };
     }
 
     /// Appends the given `char` to the end of this `CUtf8Buf`.
     #[inline]
     pub fn push(&mut self, c: char) {
-        self.with_string(|inner| inner.push(c));
+        let mut buf = [0; 4];
+        self.push_str(c.encode_utf8(&mut buf));
+    }
+
+    /// Shortens `self` to `new_len` bytes, disregarding the trailing nul byte.
+    ///
+    /// If `new_len` is greater than the current length, this has no effect.
+    /// The capacity is unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` does not lie on a `char` boundary.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::from("Grüße");
+    /// buf.truncate(4);
+    ///
+    /// assert_eq!(buf.as_bytes_with_nul(), "Grü\0".as_bytes());
+    /// ```
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len < self.len() {
+            assert!(self.as_str().is_char_boundary(new_len));
+            // SAFETY: `new_len` is a `char` boundary, so the string stays UTF-8
+            unsafe {
+                let vec = self.0.as_mut_vec();
+                vec[new_len] = 0;
+                vec.truncate(new_len + 1);
+            }
+        }
+    }
+
+    /// Removes the last `char` from `self` and returns it, or `None` if `self`
+    /// is empty.
+    ///
+    /// The trailing nul byte is never removed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::from("hé");
+    ///
+    /// assert_eq!(buf.pop(), Some('é'));
+    /// assert_eq!(buf.pop(), Some('h'));
+    /// assert_eq!(buf.pop(), None);
+    /// assert_eq!(buf.as_bytes_with_nul(), b"\0");
+    /// ```
+    #[inline]
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+        let new_len = self.len() - c.len_utf8();
+        self.truncate(new_len);
+        Some(c)
+    }
+
+    /// Removes the contents of `self`, leaving only the trailing nul byte.
+    ///
+    /// The capacity is unchanged.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Inserts `c` at the byte position `idx`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InteriorNul`](enum.Error.html#variant.InteriorNul) if
+    /// `c` is a nul character, in which case nothing is inserted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is greater than the length of `self` or does not lie on
+    /// a `char` boundary.
+    #[inline]
+    pub fn insert(&mut self, idx: usize, c: char) -> Result<(), Error> {
+        let mut buf = [0; 4];
+        self.insert_str(idx, c.encode_utf8(&mut buf))
+    }
+
+    /// Inserts `s` at the byte position `idx`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InteriorNul`](enum.Error.html#variant.InteriorNul) if
+    /// `s` contains a nul byte, in which case nothing is inserted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is greater than the length of `self` or does not lie on
+    /// a `char` boundary.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::from("/bin");
+    /// buf.insert_str(0, "/usr").unwrap();
+    /// buf.insert(buf.len(), '/').unwrap();
+    ///
+    /// assert_eq!(buf.as_str(), "/usr/bin/");
+    /// assert!(buf.insert_str(0, "\0").is_err());
+    /// ```
+    pub fn insert_str(&mut self, idx: usize, s: &str) -> Result<(), Error> {
+        assert!(self.as_str().is_char_boundary(idx));
+        check_no_nul(s.as_bytes())?;
+        // `idx` is at most the position of the nul byte, which stays last
+        self.0.insert_str(idx, s);
+        Ok(())
+    }
+
+    /// Removes the `char` at the byte position `idx` and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is not less than the length of `self`, or if it does
+    /// not lie on a `char` boundary.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::from("añb");
+    ///
+    /// assert_eq!(buf.remove(1), 'ñ');
+    /// assert_eq!(buf.as_str(), "ab");
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, idx: usize) -> char {
+        // Panics before `self` is changed, so the nul byte can never be removed
+        if self.as_str()[idx..].is_empty() {
+            panic!("cannot remove a char from the end of a string");
+        }
+        self.0.remove(idx)
+    }
+
+    /// Retains only the `char`s for which `f` returns `true`.
+    ///
+    /// The trailing nul byte is always kept and is not passed to `f`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::from("a-b_c d");
+    /// buf.retain(char::is_alphanumeric);
+    ///
+    /// assert_eq!(buf.as_bytes_with_nul(), b"abcd\0");
+    /// ```
+    ///
+    /// If `f` panics, the characters that were not visited yet are dropped,
+    /// but the trailing nul byte is kept:
+    ///
+    /// ```
+    /// use std::panic::{self, AssertUnwindSafe};
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::from("ab!cd");
+    /// let result = panic::catch_unwind(AssertUnwindSafe(|| {
+    ///     buf.retain(|c| c != '!' || panic!("unexpected '!'"));
+    /// }));
+    ///
+    /// assert!(result.is_err());
+    /// assert_eq!(buf.as_bytes_with_nul(), b"ab\0");
+    /// assert_eq!(buf.as_c_str().to_bytes(), b"ab");
+    /// ```
+    #[inline]
+    pub fn retain<F: FnMut(char) -> bool>(&mut self, f: F) {
+        // `String::retain` drops the unvisited tail if `f` panics, so the nul
+        // byte is removed up front and restored by the guard
+        self.with_string(|s| s.retain(f));
+    }
+
+    /// Replaces the bytes in `range` with `replace_with`.
+    ///
+    /// The range cannot include the trailing nul byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InteriorNul`](enum.Error.html#variant.InteriorNul) if
+    /// `replace_with` contains a nul byte, in which case nothing is replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds of [`as_str`], or if its start or
+    /// end do not lie on a `char` boundary.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::from("Hello, world!");
+    /// buf.replace_range(7..12, "C").unwrap();
+    ///
+    /// assert_eq!(buf.as_str(), "Hello, C!");
+    /// ```
+    ///
+    /// [`as_str`]: struct.CUtf8.html#method.as_str
+    pub fn replace_range<R>(&mut self, range: R, replace_with: &str) -> Result<(), Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).expect("range start out of bounds"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("range end out of bounds"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+        assert!(end <= len, "range end out of bounds");
+
+        check_no_nul(replace_with.as_bytes())?;
+        self.0.replace_range(start..end, replace_with);
+        Ok(())
+    }
+
+    /// Returns the number of bytes `self` can hold without reallocating,
+    /// disregarding the trailing nul byte.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::with_capacity(10);
+    /// assert!(buf.capacity() >= 10);
+    ///
+    /// let ptr = buf.as_ptr();
+    /// buf.push_str("0123456789");
+    /// assert_eq!(buf.as_ptr(), ptr);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity() - 1
+    }
+
+    /// Reserves capacity for at least `additional` more bytes to be appended,
+    /// in addition to the trailing nul byte.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
+
+    /// Reserves capacity for exactly `additional` more bytes to be appended,
+    /// in addition to the trailing nul byte.
+    ///
+    /// The allocator may still give more space than requested.
+    #[inline]
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.0.reserve_exact(additional);
+    }
+
+    /// Tries to reserve capacity for at least `additional` more bytes to be
+    /// appended, in addition to the trailing nul byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the capacity overflows or if the allocator reports
+    /// a failure, in which case `self` is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::new();
+    /// buf.try_reserve(16).unwrap();
+    /// assert!(buf.capacity() >= 16);
+    ///
+    /// assert!(buf.try_reserve(usize::MAX).is_err());
+    /// ```
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.0.try_reserve(additional)
+    }
+
+    /// Tries to reserve capacity for exactly `additional` more bytes to be
+    /// appended, in addition to the trailing nul byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the capacity overflows or if the allocator reports
+    /// a failure, in which case `self` is unchanged.
+    #[inline]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.0.try_reserve_exact(additional)
+    }
+
+    /// Shrinks the capacity of `self` to match its length plus the trailing
+    /// nul byte.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit();
+    }
+
+    /// Shrinks the capacity of `self` to at least `min_capacity` bytes, in
+    /// addition to the trailing nul byte.
+    ///
+    /// The capacity never drops below what is needed for the current length.
+    #[inline]
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.0.shrink_to(min_capacity.saturating_add(1));
+    }
+
+    /// Appends all of the string slices in `strs` onto the end of this
+    /// `CUtf8Buf`, reserving space for all of them at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InteriorNul`](enum.Error.html#variant.InteriorNul) if
+    /// any of `strs` contains a nul byte, with its position in `strs` as if
+    /// they were joined, in which case nothing is appended.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut buf = CUtf8Buf::from("/usr");
+    /// buf.extend_from_strs(&["/", "local", "/", "bin"]).unwrap();
+    /// assert_eq!(buf.as_str(), "/usr/local/bin");
+    ///
+    /// assert!(buf.extend_from_strs(&["/", "b\0d"]).is_err());
+    /// assert_eq!(buf.as_str(), "/usr/local/bin");
+    /// ```
+    pub fn extend_from_strs(&mut self, strs: &[&str]) -> Result<(), Error> {
+        let mut len = 0;
+        for s in strs {
+            if let Err(Error::InteriorNul { position }) = check_no_nul(s.as_bytes()) {
+                let position = len + position;
+                return Err(Error::InteriorNul { position });
+            }
+            len += s.len();
+        }
+        self.reserve(len);
+        for s in strs {
+            self.push_str(s);
+        }
+        Ok(())
     }
 
     /// Converts `self` into a native UTF-8 encoded Rust
@@ -290,6 +1831,38 @@ impl CUtf8Buf {
         self.0
     }
 
+    /// Converts `self` into an iterator over its `char`s, without the trailing
+    /// nul byte.
+    ///
+    /// Unlike [`chars`], the iterator owns the buffer, so it can be returned
+    /// or stored without keeping `self` alive separately.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let mut chars = CUtf8Buf::from("Grüß").into_chars();
+    ///
+    /// assert_eq!(chars.next(), Some('G'));
+    /// assert_eq!(chars.next_back(), Some('ß'));
+    /// assert_eq!(chars.as_str(), "rü");
+    /// assert_eq!(chars.collect::<String>(), "rü");
+    /// ```
+    ///
+    /// [`chars`]: https://doc.rust-lang.org/std/primitive.str.html#method.chars
+    #[inline]
+    pub fn into_chars(self) -> IntoChars {
+        let string = self.into_string();
+        IntoChars {
+            end: string.len(),
+            start: 0,
+            string,
+        }
+    }
+
     /// Converts `self` into its underlying bytes.
     #[inline]
     pub fn into_bytes(self) -> Vec<u8> {
@@ -303,4 +1876,218 @@ impl CUtf8Buf {
     pub fn into_bytes_with_nul(self) -> Vec<u8> {
         self.into_string_with_nul().into()
     }
+
+    /// Transfers ownership of `self` to a C caller, returning a pointer to the
+    /// start of the raw C string.
+    ///
+    /// The pointer must be passed back to [`from_raw`](#method.from_raw) to be
+    /// freed, as with [`CString::into_raw`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InteriorNul`] if the string contains a nul byte before
+    /// its end, since its length could then not be recovered with `strlen`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::ffi::CStr;
+    /// use c_utf8::CUtf8Buf;
+    ///
+    /// let raw = CUtf8Buf::from("owned by C").into_raw().unwrap();
+    ///
+    /// let c = unsafe { CStr::from_ptr(raw) };
+    /// assert_eq!(c.to_str(), Ok("owned by C"));
+    ///
+    /// let buf = unsafe { CUtf8Buf::from_raw(raw) };
+    /// assert_eq!(buf.as_str(), "owned by C");
+    ///
+    /// assert!(CUtf8Buf::from("a\0b").into_raw().is_err());
+    /// ```
+    ///
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    /// [`CString::into_raw`]:  https://doc.rust-lang.org/std/ffi/struct.CString.html#method.into_raw
+    #[inline]
+    pub fn into_raw(self) -> Result<*mut c_char, Error> {
+        CString::try_from(self).map(CString::into_raw)
+    }
+
+    /// Retakes ownership of a C string that was transferred to C with
+    /// [`into_raw`](#method.into_raw).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by `CUtf8Buf::into_raw`, and must not be
+    /// used again after this call. The C side must not have changed the length
+    /// of the string, or written bytes that are not UTF-8.
+    #[inline]
+    pub unsafe fn from_raw(ptr: *mut c_char) -> CUtf8Buf {
+        let bytes = CString::from_raw(ptr).into_bytes_with_nul();
+        CUtf8Buf(String::from_utf8_unchecked(bytes))
+    }
+}
+
+/// An owning iterator over the `char`s of a [`CUtf8Buf`].
+///
+/// This is created by [`CUtf8Buf::into_chars`].
+///
+/// [`CUtf8Buf`]: struct.CUtf8Buf.html
+/// [`CUtf8Buf::into_chars`]: struct.CUtf8Buf.html#method.into_chars
+#[derive(Clone)]
+pub struct IntoChars {
+    string: String,
+    start: usize,
+    end: usize,
+}
+
+impl IntoChars {
+    /// Returns the remaining characters as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.string[self.start..self.end]
+    }
+}
+
+impl fmt::Debug for IntoChars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IntoChars").field(&self.as_str()).finish()
+    }
+}
+
+impl Iterator for IntoChars {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next()?;
+        self.start += c.len_utf8();
+        Some(c)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        ((len + 3) / 4, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.as_str().chars().count()
+    }
+}
+
+impl DoubleEndedIterator for IntoChars {
+    #[inline]
+    fn next_back(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+        self.end -= c.len_utf8();
+        Some(c)
+    }
+}
+
+impl FusedIterator for IntoChars {}
+
+/// An iterator over the nul-terminated strings in a stream.
+///
+/// This is created by [`CUtf8Buf::records`].
+///
+/// [`CUtf8Buf::records`]: struct.CUtf8Buf.html#method.records
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct Records<R> {
+    reader: R,
+}
+
+#[cfg(feature = "std")]
+impl<R> Records<R> {
+    /// Returns the underlying reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = io::Result<CUtf8Buf>;
+
+    fn next(&mut self) -> Option<io::Result<CUtf8Buf>> {
+        loop {
+            return match self.reader.fill_buf() {
+                Ok([]) => None,
+                Ok(_) => Some(CUtf8Buf::read_from(&mut self.reader)),
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => Some(Err(err)),
+            };
+        }
+    }
+}
+
+/// Returns `bytes` without its trailing nul byte, if it has one.
+#[inline]
+fn without_nul(bytes: &[u8]) -> &[u8] {
+    match bytes {
+        [rest @ .., 0] => rest,
+        _ => bytes,
+    }
+}
+
+#[cfg(feature = "bidi")]
+fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+fn utf16_units(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> impl Iterator<Item = u16> + '_ {
+    bytes
+        .chunks_exact(2)
+        .map(move |pair| from_bytes([pair[0], pair[1]]))
+}
+
+/// Returns the code units of a nul-terminated wide string, without the nul.
+unsafe fn wide_units<'a>(ptr: *const u16) -> &'a [u16] {
+    let mut len = 0;
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+    slice::from_raw_parts(ptr, len)
+}
+
+fn decode_utf16<I: Iterator<Item = u16>>(units: I) -> Result<CUtf8Buf, Error> {
+    let mut s = String::with_capacity(units.size_hint().0 + 1);
+    let mut chars = char::decode_utf16(units).peekable();
+    // The position in bytes of UTF-16 input
+    let mut position = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            // Allow for a trailing nul terminator
+            Ok('\0') if chars.peek().is_none() => break,
+            Ok('\0') => return Err(Error::InteriorNul { position }),
+            Ok(c) => {
+                s.push(c);
+                position += 2 * c.len_utf16();
+            }
+            Err(_) => return Err(Error::Utf16),
+        }
+    }
+    Ok(CUtf8Buf::from_string(s))
+}
+
+fn decode_utf16_lossy<I: Iterator<Item = u16>>(units: I, odd: bool) -> CUtf8Buf {
+    let mut s = String::with_capacity(units.size_hint().0 + 1);
+    for c in char::decode_utf16(units) {
+        match c {
+            Ok('\0') => return CUtf8Buf::from_string(s),
+            Ok(c) => s.push(c),
+            Err(_) => s.push(char::REPLACEMENT_CHARACTER),
+        }
+    }
+    if odd {
+        s.push(char::REPLACEMENT_CHARACTER);
+    }
+    CUtf8Buf::from_string(s)
 }
@@ -0,0 +1,97 @@
+use core::ffi::{c_char, CStr};
+use core::fmt;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use crate::c_utf8::CUtf8;
+
+/// A non-null pointer to the start of a borrowed C string, for use as a field
+/// of `#[repr(C)]` structs.
+///
+/// This type is `#[repr(transparent)]` over `NonNull<c_char>`, which makes it
+/// layout-compatible with `const char*` fields of C structs, while its
+/// lifetime ties it to the [`CUtf8`](struct.CUtf8.html) it was created from.
+/// `Option<CUtf8Ptr>` has the same layout and uses null for `None`.
+///
+/// Like `NonNull`, equality and hashing compare pointer addresses, not the
+/// strings being pointed to. Compare the results of
+/// [`as_c_utf8`](#method.as_c_utf8) to compare contents.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// use c_utf8::CUtf8Ptr;
+///
+/// #[repr(C)]
+/// #[derive(Clone, Copy)]
+/// struct Header<'a> {
+///     name: CUtf8Ptr<'a>,
+///     value: Option<CUtf8Ptr<'a>>,
+/// }
+///
+/// let value = c_utf8!("text/plain");
+/// let header = Header {
+///     name: CUtf8Ptr::new(c_utf8!("Content-Type")),
+///     value: Some(CUtf8Ptr::new(value)),
+/// };
+///
+/// assert_eq!(header.name.as_c_utf8().as_str(), "Content-Type");
+/// assert_eq!(header.value.unwrap().as_c_utf8(), value);
+/// # }
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CUtf8Ptr<'a> {
+    ptr: NonNull<c_char>,
+    marker: PhantomData<&'a CUtf8>,
+}
+
+// SAFETY: The pointer is created from a `&'a CUtf8`, which is `Send` and `Sync`
+unsafe impl<'a> Send for CUtf8Ptr<'a> {}
+unsafe impl<'a> Sync for CUtf8Ptr<'a> {}
+
+impl<'a> From<&'a CUtf8> for CUtf8Ptr<'a> {
+    #[inline]
+    fn from(c: &'a CUtf8) -> CUtf8Ptr<'a> {
+        CUtf8Ptr::new(c)
+    }
+}
+
+impl<'a> fmt::Debug for CUtf8Ptr<'a> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_c_utf8().fmt(f)
+    }
+}
+
+impl<'a> CUtf8Ptr<'a> {
+    /// Creates a new pointer to the start of `c`.
+    #[inline]
+    pub const fn new(c: &'a CUtf8) -> CUtf8Ptr<'a> {
+        CUtf8Ptr {
+            // SAFETY: References are never null
+            ptr: unsafe { NonNull::new_unchecked(c.as_ptr() as *mut c_char) },
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the raw pointer to the start of the C string.
+    #[inline]
+    pub const fn as_ptr(self) -> *const c_char {
+        self.ptr.as_ptr()
+    }
+
+    /// Returns the C string being pointed to.
+    #[inline]
+    pub fn as_c_utf8(self) -> &'a CUtf8 {
+        // SAFETY: The pointer was created from a `&'a CUtf8`, so its bytes
+        // are live and unchanged for `'a`, and `strlen` stops at a nul byte
+        // within them (the terminator at the latest). Nul bytes lie on `char`
+        // boundaries, so the bytes before it are UTF-8 that was validated
+        // when that `CUtf8` was created.
+        unsafe { CUtf8::from_c_str_unchecked(CStr::from_ptr(self.as_ptr())) }
+    }
+}
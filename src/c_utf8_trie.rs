@@ -0,0 +1,192 @@
+use alloc::string::String;
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::iter::FromIterator;
+use core::mem;
+
+use crate::c_utf8::CUtf8;
+
+/// A prefix map keyed by C strings, for finding the longest key that a string
+/// starts with.
+///
+/// Keys are stored contiguously in a single buffer, each with its own trailing
+/// nul byte, so a matched key can be passed straight back to C. This is useful
+/// for dispatching on file extensions, URI schemes, or command prefixes
+/// received from C callbacks.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// use c_utf8::CUtf8Trie;
+///
+/// let mut schemes = CUtf8Trie::new();
+/// schemes.insert(c_utf8!("http"), 80);
+/// schemes.insert(c_utf8!("https"), 443);
+///
+/// let (scheme, port) = schemes
+///     .longest_prefix_match(c_utf8!("https://example.com"))
+///     .unwrap();
+///
+/// assert_eq!(scheme.as_bytes_with_nul(), b"https\0");
+/// assert_eq!(*port, 443);
+///
+/// assert_eq!(schemes.longest_prefix_match(c_utf8!("ftp://")), None);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CUtf8Trie<V> {
+    /// Every key followed by its nul byte.
+    keys: String,
+    /// The key range within `keys` (including the nul byte) and the value.
+    entries: Vec<(usize, usize, V)>,
+    /// The root node is always at index 0.
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Default)]
+struct Node {
+    /// Edges sorted by byte, to the index of the child node.
+    children: Vec<(u8, usize)>,
+    /// The index of the entry whose key ends at this node.
+    entry: Option<usize>,
+}
+
+impl Node {
+    #[inline]
+    fn child(&self, byte: u8) -> Result<usize, usize> {
+        self.children.binary_search_by_key(&byte, |&(b, _)| b)
+    }
+}
+
+impl<V> Default for CUtf8Trie<V> {
+    #[inline]
+    fn default() -> CUtf8Trie<V> {
+        CUtf8Trie::new()
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for CUtf8Trie<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: AsRef<CUtf8>, V> FromIterator<(K, V)> for CUtf8Trie<V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> CUtf8Trie<V> {
+        let mut trie = CUtf8Trie::new();
+        trie.extend(iter);
+        trie
+    }
+}
+
+impl<K: AsRef<CUtf8>, V> Extend<(K, V)> for CUtf8Trie<V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<V> CUtf8Trie<V> {
+    /// Creates a new empty trie.
+    #[inline]
+    pub fn new() -> CUtf8Trie<V> {
+        CUtf8Trie {
+            keys: String::new(),
+            entries: Vec::new(),
+            nodes: vec![Node::default()],
+        }
+    }
+
+    /// Returns the number of keys in the trie.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the trie contains no keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts `value` under `key`, returning the previous value of `key` if
+    /// there was one.
+    ///
+    /// The stored key keeps its original spelling when it is replaced.
+    pub fn insert<K: AsRef<CUtf8>>(&mut self, key: K, value: V) -> Option<V> {
+        let key = key.as_ref();
+        let mut node = 0;
+        for &byte in key.as_bytes() {
+            node = match self.nodes[node].child(byte) {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children.insert(i, (byte, child));
+                    child
+                }
+            };
+        }
+
+        if let Some(entry) = self.nodes[node].entry {
+            return Some(mem::replace(&mut self.entries[entry].2, value));
+        }
+
+        let start = self.keys.len();
+        self.keys.push_str(key.as_str_with_nul());
+        self.nodes[node].entry = Some(self.entries.len());
+        self.entries.push((start, self.keys.len(), value));
+        None
+    }
+
+    /// Returns the value stored under exactly `key`.
+    pub fn get(&self, key: &CUtf8) -> Option<&V> {
+        let mut node = &self.nodes[0];
+        for &byte in key.as_bytes() {
+            let i = node.child(byte).ok()?;
+            node = &self.nodes[node.children[i].1];
+        }
+        node.entry.map(|entry| &self.entries[entry].2)
+    }
+
+    /// Returns `true` if the trie contains exactly `key`.
+    #[inline]
+    pub fn contains_key(&self, key: &CUtf8) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the longest key that `s` starts with, along with its value.
+    ///
+    /// The trailing nul byte of `s` is not considered, so the empty key
+    /// matches any string.
+    pub fn longest_prefix_match(&self, s: &CUtf8) -> Option<(&CUtf8, &V)> {
+        let mut node = &self.nodes[0];
+        let mut found = node.entry;
+        for &byte in s.as_bytes() {
+            match node.child(byte) {
+                Ok(i) => node = &self.nodes[node.children[i].1],
+                Err(_) => break,
+            }
+            found = node.entry.or(found);
+        }
+        found.map(|entry| self.entry(entry))
+    }
+
+    /// Returns an iterator over the keys and values, in insertion order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&CUtf8, &V)> + '_ {
+        (0..self.entries.len()).map(move |entry| self.entry(entry))
+    }
+
+    #[inline]
+    fn entry(&self, entry: usize) -> (&CUtf8, &V) {
+        let (start, end, ref value) = self.entries[entry];
+        // SAFETY: every key is stored in `keys` with its trailing nul byte
+        let key = unsafe { CUtf8::from_str_unchecked(&self.keys[start..end]) };
+        (key, value)
+    }
+}
@@ -0,0 +1,116 @@
+use core::fmt;
+
+use crate::c_utf8::CUtf8;
+use crate::error::Error;
+
+/// A [`fmt::Write`] adapter that formats into a borrowed byte buffer and
+/// produces a [`&CUtf8`] when finished.
+///
+/// No allocations are made, so this is available without the `alloc` feature.
+/// One byte of the buffer is always kept for the trailing nul byte. See
+/// [`c_utf8_fmt!`] for a shorthand.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::Write;
+/// use c_utf8::CUtf8Writer;
+///
+/// let mut buf = [0; 32];
+/// let mut w = CUtf8Writer::new(&mut buf);
+/// write!(w, "x={}", 42).unwrap();
+///
+/// let s = w.finish().unwrap();
+/// assert_eq!(s.as_bytes_with_nul(), b"x=42\0");
+/// ```
+///
+/// [`fmt::Write`]:  https://doc.rust-lang.org/core/fmt/trait.Write.html
+/// [`&CUtf8`]:      struct.CUtf8.html
+/// [`c_utf8_fmt!`]: macro.c_utf8_fmt.html
+#[derive(Debug)]
+pub struct CUtf8Writer<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+    error: Option<Error>,
+}
+
+impl<'a> CUtf8Writer<'a> {
+    /// Creates a new writer that formats into `buf`.
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> CUtf8Writer<'a> {
+        CUtf8Writer {
+            buf,
+            len: 0,
+            error: None,
+        }
+    }
+
+    /// Returns the string written so far, without a trailing nul byte.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: only whole `str`s are copied into `buf[..len]`
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+
+    /// Appends the trailing nul byte and returns the written C string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Capacity`] if the buffer was too small for everything
+    /// written, or [`Error::InteriorNul`] if a nul byte was written.
+    ///
+    /// [`Error::Capacity`]:    enum.Error.html#variant.Capacity
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    pub fn finish(self) -> Result<&'a CUtf8, Error> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let bytes = match self.buf.get_mut(..=self.len) {
+            Some(bytes) => bytes,
+            None => return Err(Error::Capacity),
+        };
+        bytes[self.len] = 0;
+        // SAFETY: `bytes` is UTF-8 with no nul bytes, followed by a nul byte
+        Ok(unsafe { CUtf8::from_bytes_unchecked(bytes) })
+    }
+
+    /// Formats `args` into `buf` and returns the written C string.
+    ///
+    /// This is what [`c_utf8_fmt!`](macro.c_utf8_fmt.html) expands to.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`finish`](#method.finish), or
+    /// [`Error::Fmt`](enum.Error.html#variant.Fmt) if a formatting trait
+    /// implementation returned an error.
+    pub fn format(buf: &'a mut [u8], args: fmt::Arguments) -> Result<&'a CUtf8, Error> {
+        let mut w = CUtf8Writer::new(buf);
+        match fmt::Write::write_fmt(&mut w, args) {
+            Err(_) if w.error.is_none() => Err(Error::Fmt),
+            _ => w.finish(),
+        }
+    }
+}
+
+impl<'a> fmt::Write for CUtf8Writer<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.error.is_some() {
+            return Err(fmt::Error);
+        }
+        if let Some(nul) = s.bytes().position(|b| b == 0) {
+            self.error = Some(Error::InteriorNul {
+                position: self.len + nul,
+            });
+            return Err(fmt::Error);
+        }
+        // Keep room for the nul byte
+        let end = self.len + s.len();
+        if end >= self.buf.len() {
+            self.error = Some(Error::Capacity);
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+//! Comparisons between C strings and native Rust strings, disregarding the
+//! trailing nul byte.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate core;
+//! # #[macro_use] extern crate c_utf8; fn main() {
+//! use core::ffi::CStr;
+//!
+//! let s = c_utf8!("key");
+//! let c = CStr::from_bytes_with_nul(b"key\0").unwrap();
+//!
+//! assert_eq!(s, "key");
+//! assert_eq!(s, c);
+//! assert!(s < "keys" && "kex" < s);
+//! # }
+//! ```
+#![cfg_attr(
+    feature = "alloc",
+    doc = r#"
+Owned strings compare the same way:
+
+```
+# extern crate core;
+# #[macro_use] extern crate c_utf8; fn main() {
+use core::ffi::CStr;
+use c_utf8::CUtf8Buf;
+
+let s = c_utf8!("key");
+let c = CStr::from_bytes_with_nul(b"key\0").unwrap();
+
+assert_eq!(String::from("key"), *s);
+assert_eq!(CUtf8Buf::from("key"), c);
+# }
+```
+"#
+)]
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::ffi::CStr;
+
+use crate::c_utf8::CUtf8;
+#[cfg(feature = "alloc")]
+use crate::c_utf8_buf::CUtf8Buf;
+
+/// A string type that can be compared by its bytes without a nul terminator.
+trait CmpBytes {
+    fn cmp_bytes(&self) -> &[u8];
+}
+
+impl<'a, T: CmpBytes + ?Sized> CmpBytes for &'a T {
+    #[inline]
+    fn cmp_bytes(&self) -> &[u8] {
+        (**self).cmp_bytes()
+    }
+}
+
+impl CmpBytes for str {
+    #[inline]
+    fn cmp_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl CmpBytes for CStr {
+    #[inline]
+    fn cmp_bytes(&self) -> &[u8] {
+        self.to_bytes()
+    }
+}
+
+impl CmpBytes for CUtf8 {
+    #[inline]
+    fn cmp_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl CmpBytes for String {
+    #[inline]
+    fn cmp_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl CmpBytes for CUtf8Buf {
+    #[inline]
+    fn cmp_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Implements `PartialEq` and `PartialOrd` between both types, both ways.
+macro_rules! impl_cmp {
+    ($($(#[$attr:meta])* $lhs:ty, $rhs:ty;)+) => { $(
+        $(#[$attr])*
+        impl<'a> PartialEq<$rhs> for $lhs {
+            #[inline]
+            fn eq(&self, other: &$rhs) -> bool {
+                self.cmp_bytes() == other.cmp_bytes()
+            }
+        }
+
+        $(#[$attr])*
+        impl<'a> PartialEq<$lhs> for $rhs {
+            #[inline]
+            fn eq(&self, other: &$lhs) -> bool {
+                self.cmp_bytes() == other.cmp_bytes()
+            }
+        }
+
+        $(#[$attr])*
+        impl<'a> PartialOrd<$rhs> for $lhs {
+            #[inline]
+            fn partial_cmp(&self, other: &$rhs) -> Option<Ordering> {
+                self.cmp_bytes().partial_cmp(other.cmp_bytes())
+            }
+        }
+
+        $(#[$attr])*
+        impl<'a> PartialOrd<$lhs> for $rhs {
+            #[inline]
+            fn partial_cmp(&self, other: &$lhs) -> Option<Ordering> {
+                self.cmp_bytes().partial_cmp(other.cmp_bytes())
+            }
+        }
+    )+ };
+}
+
+impl_cmp! {
+    CUtf8, str;
+    CUtf8, &'a str;
+    &'a CUtf8, str;
+    CUtf8, CStr;
+    CUtf8, &'a CStr;
+    &'a CUtf8, CStr;
+
+    #[cfg(feature = "alloc")] CUtf8, String;
+    #[cfg(feature = "alloc")] &'a CUtf8, String;
+    #[cfg(feature = "alloc")] CUtf8Buf, CUtf8;
+    #[cfg(feature = "alloc")] CUtf8Buf, &'a CUtf8;
+    #[cfg(feature = "alloc")] CUtf8Buf, str;
+    #[cfg(feature = "alloc")] CUtf8Buf, &'a str;
+    #[cfg(feature = "alloc")] CUtf8Buf, String;
+    #[cfg(feature = "alloc")] CUtf8Buf, CStr;
+    #[cfg(feature = "alloc")] CUtf8Buf, &'a CStr;
+}
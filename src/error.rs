@@ -1,48 +1,155 @@
-use core::ffi::FromBytesWithNulError;
 use core::fmt;
 use core::str::Utf8Error;
 
+/// The `Error` trait, which is only in `core` on nightly before Rust 1.81.
+#[cfg(feature = "error_in_core")]
+pub(crate) use core::error::Error as StdError;
+#[cfg(all(feature = "std", not(feature = "error_in_core")))]
+pub(crate) use std::error::Error as StdError;
+
 /// The error for converting types to [`CUtf8`](struct.CUtf8.html).
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error {
-    /// An error indicating that the nul byte was not at the end.
-    Nul,
+    /// An error indicating that there was no nul byte at the end.
+    MissingNul,
+    /// An error indicating that there was a nul byte before the end.
+    InteriorNul {
+        /// The byte position of the nul byte.
+        position: usize,
+    },
     /// An error indicating that input bytes were not encoded as UTF-8.
     Utf8(Utf8Error),
+    /// An error indicating that a destination buffer was too small.
+    Capacity,
+    /// An error indicating that input data was not encoded as UTF-16.
+    Utf16,
+    /// An error indicating that a formatting trait implementation returned an
+    /// error.
+    Fmt,
 }
 
-const NUL_ERROR: &str = "Missing nul byte at the end of the string";
-
-impl From<Utf8Error> for Error {
+impl Error {
+    /// Returns the byte position in the input at which the error was found, if
+    /// known.
+    ///
+    /// For [`Utf8`](#variant.Utf8) errors, this is the length of the valid
+    /// prefix, as given by [`Utf8Error::valid_up_to`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::{CUtf8, Error};
+    ///
+    /// let err = CUtf8::from_bytes(b"key\0value\0").unwrap_err();
+    /// assert_eq!(err, Error::InteriorNul { position: 3 });
+    /// assert_eq!(err.position(), Some(3));
+    ///
+    /// let err = CUtf8::from_bytes(b"ab\xFF\0").unwrap_err();
+    /// assert_eq!(err.position(), Some(2));
+    ///
+    /// let err = CUtf8::from_bytes(b"abc").unwrap_err();
+    /// assert_eq!(err, Error::MissingNul);
+    /// assert_eq!(err.position(), None);
+    /// ```
+    ///
+    /// [`Utf8Error::valid_up_to`]: https://doc.rust-lang.org/core/str/struct.Utf8Error.html#method.valid_up_to
     #[inline]
-    fn from(err: Utf8Error) -> Error {
-        Error::Utf8(err)
+    pub fn position(&self) -> Option<usize> {
+        match *self {
+            Error::InteriorNul { position } => Some(position),
+            Error::Utf8(err) => Some(err.valid_up_to()),
+            _ => None,
+        }
+    }
+}
+
+/// Returns an error if there is a nul byte in `bytes`.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn check_no_nul(bytes: &[u8]) -> Result<(), Error> {
+    match bytes.iter().position(|&b| b == 0) {
+        Some(position) => Err(Error::InteriorNul { position }),
+        None => Ok(()),
     }
 }
 
-impl From<FromBytesWithNulError> for Error {
+const MISSING_NUL_ERROR: &str = "Missing nul byte at the end of the string";
+
+const CAPACITY_ERROR: &str = "Destination buffer is too small for the string";
+
+const UTF16_ERROR: &str = "Invalid UTF-16 in input";
+
+const FMT_ERROR: &str = "A formatting trait implementation returned an error";
+
+impl From<Utf8Error> for Error {
     #[inline]
-    fn from(_: FromBytesWithNulError) -> Error {
-        Error::Nul
+    fn from(err: Utf8Error) -> Error {
+        Error::Utf8(err)
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Error::Nul => NUL_ERROR.fmt(f),
+            Error::MissingNul => MISSING_NUL_ERROR.fmt(f),
+            Error::InteriorNul { position } => {
+                write!(f, "Interior nul byte at position {}", position)
+            }
             Error::Utf8(err) => err.fmt(f),
+            Error::Capacity => CAPACITY_ERROR.fmt(f),
+            Error::Utf16 => UTF16_ERROR.fmt(f),
+            Error::Fmt => FMT_ERROR.fmt(f),
         }
     }
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for Error {
+#[cfg(any(feature = "std", feature = "error_in_core"))]
+impl StdError for Error {
     #[inline]
-    fn cause(&self) -> Option<&dyn std::error::Error> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match *self {
             Error::Utf8(ref err) => Some(err),
             _ => None,
         }
     }
 }
+
+/// The error for parsing escaped strings with
+/// [`CUtf8Buf::from_escaped`](struct.CUtf8Buf.html#method.from_escaped).
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnescapeError {
+    /// An error indicating that the input or an escape sequence produced a nul
+    /// byte.
+    Nul,
+    /// An error indicating that a backslash was followed by an unknown
+    /// character.
+    UnknownEscape(char),
+    /// An error indicating that a `\xNN` escape was malformed or out of the
+    /// ASCII range.
+    InvalidHexEscape,
+    /// An error indicating that a `\u{...}` escape was malformed or not a valid
+    /// `char`.
+    InvalidUnicodeEscape,
+    /// An error indicating that the input ended with a backslash.
+    TrailingBackslash,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for UnescapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UnescapeError::Nul => "Escaped string contains a nul byte".fmt(f),
+            UnescapeError::UnknownEscape(c) => write!(f, "Unknown escape sequence '\\{}'", c),
+            UnescapeError::InvalidHexEscape => "Invalid '\\x' escape sequence".fmt(f),
+            UnescapeError::InvalidUnicodeEscape => "Invalid '\\u' escape sequence".fmt(f),
+            UnescapeError::TrailingBackslash => "Missing character after backslash".fmt(f),
+        }
+    }
+}
+
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "error_in_core")))]
+impl StdError for UnescapeError {}
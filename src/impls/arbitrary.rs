@@ -0,0 +1,67 @@
+use alloc::string::String;
+
+use arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+use crate::c_utf8::CUtf8;
+use crate::c_utf8_buf::CUtf8Buf;
+
+/// Generates strings without any nul bytes before the terminator.
+impl<'a> Arbitrary<'a> for CUtf8Buf {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<CUtf8Buf> {
+        let s = <&str>::arbitrary(u)?;
+        Ok(s.chars().filter(|&c| c != '\0').collect())
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<CUtf8Buf> {
+        let s = <&str>::arbitrary_take_rest(u)?;
+        Ok(s.chars().filter(|&c| c != '\0').collect())
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        String::size_hint(depth)
+    }
+}
+
+/// Borrows the data up to and including the next nul byte, without copying.
+///
+/// This fails with `IncorrectFormat` if the data before the nul byte is not
+/// UTF-8, and with `NotEnoughData` if there is no nul byte.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate arbitrary;
+/// # extern crate c_utf8;
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use c_utf8::CUtf8;
+///
+/// let mut u = Unstructured::new(b"first\0second\0");
+///
+/// let s = <&CUtf8>::arbitrary(&mut u).unwrap();
+/// assert_eq!(s.as_bytes_with_nul(), b"first\0");
+///
+/// let s = <&CUtf8>::arbitrary(&mut u).unwrap();
+/// assert_eq!(s.as_str(), "second");
+///
+/// assert!(<&CUtf8>::arbitrary(&mut u).is_err());
+/// # fn main() {}
+/// ```
+impl<'a> Arbitrary<'a> for &'a CUtf8 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<&'a CUtf8> {
+        let len = match u.peek_bytes(u.len()) {
+            Some(data) => data.iter().position(|&b| b == 0),
+            None => None,
+        };
+        let bytes = match len {
+            Some(len) => u.bytes(len + 1)?,
+            None => return Err(Error::NotEnoughData),
+        };
+        CUtf8::from_bytes(bytes).map_err(|_| Error::IncorrectFormat)
+    }
+
+    #[inline]
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, None)
+    }
+}
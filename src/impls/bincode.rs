@@ -0,0 +1,65 @@
+use bincode::enc::{Encode, Encoder};
+use bincode::error::EncodeError;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "alloc")]
+use bincode::de::{Decode, Decoder};
+#[cfg(feature = "alloc")]
+use bincode::error::DecodeError;
+
+use crate::c_utf8::CUtf8;
+#[cfg(feature = "alloc")]
+use crate::c_utf8_buf::CUtf8Buf;
+
+impl Encode for CUtf8 {
+    #[inline]
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_str().encode(encoder)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encode for CUtf8Buf {
+    #[inline]
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        (**self).encode(encoder)
+    }
+}
+
+/// Decodes a string, rejecting any nul bytes in it.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate bincode;
+/// # extern crate c_utf8;
+/// use bincode::config;
+/// use c_utf8::CUtf8Buf;
+///
+/// let buf = CUtf8Buf::from("key");
+/// let bytes = bincode::encode_to_vec(&buf, config::standard()).unwrap();
+/// assert_eq!(bytes, b"\x03key");
+///
+/// let (decoded, _): (CUtf8Buf, _) =
+///     bincode::decode_from_slice(&bytes, config::standard()).unwrap();
+/// assert_eq!(decoded, buf);
+///
+/// let nul = bincode::encode_to_vec("k\0y", config::standard()).unwrap();
+/// assert!(bincode::decode_from_slice::<CUtf8Buf, _>(&nul, config::standard()).is_err());
+/// # fn main() {}
+/// ```
+#[cfg(feature = "alloc")]
+impl Decode for CUtf8Buf {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<CUtf8Buf, DecodeError> {
+        let s = String::decode(decoder)?;
+        if s.as_bytes().contains(&0) {
+            return Err(DecodeError::Other("C string contains a nul byte"));
+        }
+        Ok(CUtf8Buf::from_string(s))
+    }
+}
+
+#[cfg(feature = "alloc")]
+bincode::impl_borrow_decode!(CUtf8Buf);
@@ -0,0 +1,20 @@
+use defmt::{Format, Formatter};
+
+use crate::c_utf8::CUtf8;
+#[cfg(feature = "alloc")]
+use crate::c_utf8_buf::CUtf8Buf;
+
+impl Format for CUtf8 {
+    #[inline]
+    fn format(&self, f: Formatter) {
+        self.as_str().format(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Format for CUtf8Buf {
+    #[inline]
+    fn format(&self, f: Formatter) {
+        (**self).format(f)
+    }
+}
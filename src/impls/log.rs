@@ -0,0 +1,20 @@
+use log::kv::{ToValue, Value};
+
+use crate::c_utf8::CUtf8;
+#[cfg(feature = "alloc")]
+use crate::c_utf8_buf::CUtf8Buf;
+
+impl ToValue for CUtf8 {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::from(self.as_str())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToValue for CUtf8Buf {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::from(self.as_str())
+    }
+}
@@ -0,0 +1,22 @@
+//! Trait implementations for optional third-party crates.
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+#[cfg(feature = "bincode")]
+mod bincode;
+
+#[cfg(feature = "defmt")]
+mod defmt;
+
+#[cfg(feature = "log")]
+mod log;
+
+#[cfg(feature = "portable-atomic")]
+mod portable_atomic;
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+
+#[cfg(feature = "serde")]
+mod serde;
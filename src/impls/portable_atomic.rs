@@ -0,0 +1,20 @@
+use portable_atomic_util::Arc;
+
+use crate::c_utf8::CUtf8;
+use crate::c_utf8_buf::CUtf8Buf;
+
+impl<'a> From<&'a CUtf8> for Arc<CUtf8> {
+    #[inline]
+    fn from(c: &CUtf8) -> Arc<CUtf8> {
+        let arc: Arc<str> = c.as_str_with_nul().into();
+        unsafe { Arc::from_raw(Arc::into_raw(arc) as *const CUtf8) }
+    }
+}
+
+impl From<CUtf8Buf> for Arc<CUtf8> {
+    #[inline]
+    fn from(buf: CUtf8Buf) -> Arc<CUtf8> {
+        let arc: Arc<str> = buf.into_string_with_nul().into();
+        unsafe { Arc::from_raw(Arc::into_raw(arc) as *const CUtf8) }
+    }
+}
@@ -0,0 +1,23 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+
+use quickcheck::{Arbitrary, Gen};
+
+use crate::c_utf8_buf::CUtf8Buf;
+
+impl Arbitrary for CUtf8Buf {
+    fn arbitrary(g: &mut Gen) -> CUtf8Buf {
+        let s = String::arbitrary(g);
+        s.chars().filter(|&c| c != '\0').collect()
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = CUtf8Buf>> {
+        // Shrinking a `char` tends towards nul, which must not be produced
+        let shrunk = String::from(self.as_str()).shrink();
+        Box::new(
+            shrunk
+                .filter(|s| !s.contains('\0'))
+                .map(CUtf8Buf::from_string),
+        )
+    }
+}
@@ -0,0 +1,103 @@
+use serde::ser::{Serialize, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::{fmt, str};
+
+#[cfg(feature = "alloc")]
+use serde::de::{self, Deserialize, Deserializer, Unexpected, Visitor};
+
+use crate::c_utf8::CUtf8;
+#[cfg(feature = "alloc")]
+use crate::c_utf8_buf::CUtf8Buf;
+
+impl Serialize for CUtf8 {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for CUtf8Buf {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+/// Deserializes a string or bytes, rejecting any nul bytes.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate c_utf8;
+/// # extern crate serde;
+/// use c_utf8::CUtf8Buf;
+/// use serde::de::value::{Error, StrDeserializer};
+/// use serde::Deserialize;
+///
+/// let de = StrDeserializer::<Error>::new("config");
+/// let s = CUtf8Buf::deserialize(de).unwrap();
+/// assert_eq!(s.as_bytes_with_nul(), b"config\0");
+///
+/// let de = StrDeserializer::<Error>::new("inter\0ior");
+/// assert!(CUtf8Buf::deserialize(de).is_err());
+/// # fn main() {}
+/// ```
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for CUtf8Buf {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<CUtf8Buf, D::Error> {
+        deserializer.deserialize_string(CUtf8BufVisitor)
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct CUtf8BufVisitor;
+
+#[cfg(feature = "alloc")]
+impl CUtf8BufVisitor {
+    fn finish<E: de::Error>(self, s: String) -> Result<CUtf8Buf, E> {
+        if s.as_bytes().contains(&0) {
+            return Err(E::invalid_value(Unexpected::Str(&s), &self));
+        }
+        Ok(CUtf8Buf::from_string(s))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for CUtf8BufVisitor {
+    type Value = CUtf8Buf;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string with no nul bytes")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<CUtf8Buf, E> {
+        self.finish(s.into())
+    }
+
+    #[inline]
+    fn visit_string<E: de::Error>(self, s: String) -> Result<CUtf8Buf, E> {
+        self.finish(s)
+    }
+
+    fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<CUtf8Buf, E> {
+        match str::from_utf8(b) {
+            Ok(s) => self.visit_str(s),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(b), &self)),
+        }
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, b: Vec<u8>) -> Result<CUtf8Buf, E> {
+        match String::from_utf8(b) {
+            Ok(s) => self.finish(s),
+            Err(err) => Err(E::invalid_value(Unexpected::Bytes(err.as_bytes()), &self)),
+        }
+    }
+}
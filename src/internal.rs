@@ -11,3 +11,86 @@ pub const fn check_no_nul(x: &str) {
         i += 1;
     }
 }
+
+/// Returns the bytes of `x` followed by a nul byte, panicking (in `const`) if
+/// `x` contains any nul characters or `N` is not one more than its length.
+pub const fn with_nul<const N: usize>(x: &str) -> [u8; N] {
+    concat_with_nul(&[x])
+}
+
+/// Returns the total length of `strs`.
+pub const fn concat_len(strs: &[&str]) -> usize {
+    let mut len = 0;
+    let mut i = 0;
+    while i < strs.len() {
+        len += strs[i].len();
+        i += 1;
+    }
+    len
+}
+
+/// Returns the bytes of `strs` joined and followed by a nul byte, panicking (in
+/// `const`) if any of them contain nul characters or `N` is not one more than
+/// their total length.
+pub const fn concat_with_nul<const N: usize>(strs: &[&str]) -> [u8; N] {
+    assert!(concat_len(strs) + 1 == N);
+
+    let mut out = [0; N];
+    let mut pos = 0;
+    let mut i = 0;
+    while i < strs.len() {
+        check_no_nul(strs[i]);
+
+        let bytes = strs[i].as_bytes();
+        let mut j = 0;
+        while j < bytes.len() {
+            out[pos] = bytes[j];
+            pos += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Returns `bytes` as a C string without checking it.
+///
+/// # Safety
+///
+/// `bytes` must be UTF-8 with a trailing nul byte.
+pub const unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &crate::CUtf8 {
+    crate::CUtf8::from_str_unchecked(core::str::from_utf8_unchecked(bytes))
+}
+
+pub use core::ffi::{c_char, c_int};
+
+/// The body of the entry point generated by `c_main!`.
+///
+/// # Safety
+///
+/// The arguments must be those passed to a C `main` function.
+pub unsafe fn c_main(
+    argc: c_int,
+    argv: *const *const c_char,
+    envp: *const *const c_char,
+    f: fn(crate::CArgs<'static>, crate::CEnv<'static>) -> c_int,
+) -> c_int {
+    let (args, env) = match (
+        crate::CArgs::from_raw(argc, argv),
+        crate::CEnv::from_raw(envp),
+    ) {
+        (Ok(args), Ok(env)) => (args, env),
+        // Matches the conventional exit code for usage errors
+        _ => return 2,
+    };
+
+    #[cfg(feature = "std")]
+    {
+        let run = std::panic::AssertUnwindSafe(|| f(args, env));
+        // Matches the exit code of a panicking Rust `main`
+        std::panic::catch_unwind(run).unwrap_or(101)
+    }
+
+    #[cfg(not(feature = "std"))]
+    f(args, env)
+}
@@ -0,0 +1,61 @@
+use core::iter::FusedIterator;
+use core::str;
+
+use crate::c_utf8::CUtf8;
+use crate::error::Error;
+
+/// An iterator over back-to-back nul-terminated strings in a byte buffer.
+///
+/// This is created by [`CUtf8::iter_packed`].
+///
+/// [`CUtf8::iter_packed`]: struct.CUtf8.html#method.iter_packed
+#[derive(Clone, Debug)]
+pub struct IterPacked<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> IterPacked<'a> {
+    #[inline]
+    pub(crate) fn new(bytes: &'a [u8]) -> IterPacked<'a> {
+        IterPacked { bytes }
+    }
+
+    /// Returns the bytes that have not been iterated over yet.
+    #[inline]
+    pub fn remainder(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl<'a> Iterator for IterPacked<'a> {
+    type Item = Result<&'a CUtf8, Error>;
+
+    fn next(&mut self) -> Option<Result<&'a CUtf8, Error>> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let nul = match self.bytes.iter().position(|&b| b == 0) {
+            Some(nul) => nul,
+            None => {
+                self.bytes = &[];
+                return Some(Err(Error::MissingNul));
+            }
+        };
+        let (s, rest) = self.bytes.split_at(nul + 1);
+        self.bytes = rest;
+
+        // Each string is checked separately, so a bad one can be skipped
+        Some(match str::from_utf8(&s[..nul]) {
+            Ok(_) => Ok(unsafe { CUtf8::from_bytes_unchecked(s) }),
+            Err(err) => Err(Error::Utf8(err)),
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bytes.len();
+        (usize::from(len != 0), Some(len))
+    }
+}
+
+impl<'a> FusedIterator for IterPacked<'a> {}
@@ -27,6 +27,70 @@
 //! # fn main() {}
 //! ```
 //!
+//! # Cargo Features
+//!
+//! - `std` (default): enables `alloc` as well as interop with [`std`] types
+//!   such as `Path`, `OsStr`, `io::BufRead`, and `std::error::Error`.
+//!
+//! - `alloc`: enables the owned [`CUtf8Buf`] type, as well as conversions into
+//!   the shared `Rc<CUtf8>` and `Arc<CUtf8>` types.
+//!
+//! - `shell`: enables the [`shell`] module for splitting command lines into
+//!   arguments and joining them back, following POSIX or Windows rules.
+//!
+//! - `arbitrary`: implements [`arbitrary::Arbitrary`] for [`CUtf8Buf`],
+//!   generating only valid C strings, and for `&CUtf8`, borrowing the input
+//!   data up to the next nul byte. This is intended for fuzzing.
+//!
+//! - `bidi`: enables [`CUtf8::strip_bidi_controls`] and
+//!   [`CUtf8::escape_bidi_controls`] for neutralizing Unicode bidirectional
+//!   control characters in untrusted strings.
+//!
+//! - `bincode`: implements the native [bincode 2] `Encode` and `Decode`
+//!   traits, encoding strings without the trailing nul byte and rejecting nul
+//!   bytes when decoding. This is pinned to `2.0.0-rc.3`, the last release
+//!   that builds on this crate's toolchain.
+//!
+//! - `defmt`: implements [`defmt::Format`], so that strings can be logged on
+//!   embedded targets without the trailing nul byte.
+//!
+//! - `deunicode`: enables transliterating strings to ASCII via
+//!   [`CUtf8::to_ascii_lossy_translit`].
+//!
+//! - `error_in_core` (nightly): implements `core::error::Error` for the error
+//!   types without requiring `std`, so they work in `no_std` error chains.
+//!   The trait is only available in `core` on nightly before Rust 1.81.
+//!
+//! - `log`: implements [`log::kv::ToValue`], so that strings can be recorded
+//!   as structured key-value pairs without the trailing nul byte.
+//!
+//! - `memchr`: accelerates [`CUtf8::find_byte`] and [`CUtf8::rfind_byte`] with
+//!   the vectorized searches of [`memchr`].
+//!
+//! - `pattern` (nightly): implements the unstable [`Pattern`] trait for
+//!   `&CUtf8` and `&CUtf8Buf`, so they can be used as `str` search patterns.
+//!
+//! - `portable-atomic`: implements conversions into
+//!   [`portable_atomic_util::Arc<CUtf8>`], which provides shared C strings on
+//!   targets without native atomics (e.g. `thumbv6m`). See the
+//!   [`portable-atomic`] documentation for how to enable atomics on such
+//!   targets.
+//!
+//! - `quickcheck`: implements [`quickcheck::Arbitrary`] for [`CUtf8Buf`],
+//!   generating and shrinking only to valid C strings.
+//!
+//! - `serde`: implements `Serialize` for [`CUtf8`] and [`CUtf8Buf`], and
+//!   `Deserialize` for [`CUtf8Buf`], as plain strings without the trailing nul
+//!   byte and rejecting nul bytes when deserializing. Also enables the
+//!   [`trailing_nul`] serde adapter, for deserializing strings that may carry
+//!   their nul terminator.
+//!
+//! - `zerocopy`: implements the [`zerocopy`] 0.7 `AsBytes` and `Unaligned`
+//!   traits for [`CUtf8`], so it can be viewed as bytes, including its nul
+//!   terminator, and used where `zerocopy` requires unaligned types. Use
+//!   [`CUtf8::from_bytes`] or [`CUtf8::from_bytes_until_nul`] to borrow a
+//!   `&CUtf8` from a byte buffer without copying.
+//!
 //! # Examples
 //!
 //! A [`CUtf8`] slice can be created via the [`c_utf8!`](macro.c_utf8.html)
@@ -63,9 +127,32 @@
 //! [`str`]:      https://doc.rust-lang.org/std/primitive.str.html
 //! [`CUtf8`]:    struct.CUtf8.html
 //! [`CUtf8Buf`]: struct.CUtf8Buf.html
+//! [`std`]:      https://doc.rust-lang.org/std/
+//! [`shell`]:    shell/index.html
+//! [`trailing_nul`]: trailing_nul/index.html
+//!
+//! [`CUtf8::strip_bidi_controls`]:    struct.CUtf8.html#method.strip_bidi_controls
+//! [`CUtf8::escape_bidi_controls`]:   struct.CUtf8.html#method.escape_bidi_controls
+//! [`CUtf8::to_ascii_lossy_translit`]: struct.CUtf8.html#method.to_ascii_lossy_translit
+//! [`CUtf8::find_byte`]:              struct.CUtf8.html#method.find_byte
+//! [`CUtf8::rfind_byte`]:             struct.CUtf8.html#method.rfind_byte
+//! [`CUtf8::from_bytes`]:             struct.CUtf8.html#method.from_bytes
+//! [`CUtf8::from_bytes_until_nul`]:   struct.CUtf8.html#method.from_bytes_until_nul
+//! [`memchr`]:                 https://docs.rs/memchr
+//! [`Pattern`]:                https://doc.rust-lang.org/std/str/pattern/trait.Pattern.html
+//! [bincode 2]:               https://docs.rs/bincode/2.0.0-rc.3
+//! [`arbitrary::Arbitrary`]: https://docs.rs/arbitrary/1/arbitrary/trait.Arbitrary.html
+//! [`defmt::Format`]:        https://docs.rs/defmt/0.3/defmt/trait.Format.html
+//! [`log::kv::ToValue`]:      https://docs.rs/log/0.4/log/kv/trait.ToValue.html
+//! [`portable_atomic_util::Arc<CUtf8>`]: https://docs.rs/portable-atomic-util/0.2/portable_atomic_util/struct.Arc.html
+//! [`portable-atomic`]:        https://docs.rs/portable-atomic
+//! [`quickcheck::Arbitrary`]: https://docs.rs/quickcheck/1/quickcheck/trait.Arbitrary.html
+//! [`zerocopy`]:               https://docs.rs/zerocopy/0.7
 
 #![deny(missing_docs)]
 #![no_std]
+#![cfg_attr(feature = "pattern", feature(pattern))]
+#![cfg_attr(feature = "error_in_core", feature(error_in_core))]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
@@ -73,6 +160,36 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+
+#[cfg(feature = "bincode")]
+extern crate bincode;
+
+#[cfg(feature = "defmt")]
+extern crate defmt;
+
+#[cfg(feature = "deunicode")]
+extern crate deunicode;
+
+#[cfg(feature = "log")]
+extern crate log;
+
+#[cfg(feature = "memchr")]
+extern crate memchr;
+
+#[cfg(feature = "portable-atomic")]
+extern crate portable_atomic_util;
+
+#[cfg(feature = "quickcheck")]
+extern crate quickcheck;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(feature = "zerocopy")]
+extern crate zerocopy;
+
 /// Creates a [`&'static CUtf8`](struct.CUtf8.html) from a native Rust [`str`]
 /// string literal, making it much easier to work with C APIs that are strict
 /// about encoding input as UTF-8.
@@ -106,6 +223,18 @@ extern crate std;
 /// # }
 /// ```
 ///
+/// Multiple constant `&str` expressions are concatenated, which `concat!` only
+/// allows for literals:
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; use c_utf8::CUtf8; fn main() {
+/// const PREFIX: &str = "app-";
+/// const VERSION: &CUtf8 = c_utf8!(PREFIX, env!("CARGO_PKG_VERSION"), "-release");
+///
+/// assert_eq!(VERSION.as_str(), concat!("app-", env!("CARGO_PKG_VERSION"), "-release"));
+/// # }
+/// ```
+///
 /// A `c_utf8!` literal cannot contain any intermediate `\0`:
 ///
 /// ```compile_fail
@@ -113,10 +242,18 @@ extern crate std;
 /// let _fails = c_utf8!("Null\0in the middle");
 /// ```
 ///
+/// This also applies across all of the concatenated expressions:
+///
+/// ```compile_fail
+/// # use c_utf8::c_utf8;
+/// const NUL: &str = "\0";
+/// let _fails = c_utf8!("Null", NUL, "in the middle");
+/// ```
+///
 /// [`str`]: https://doc.rust-lang.org/std/primitive.str.html
 #[macro_export]
 macro_rules! c_utf8 {
-    ($s:expr) => {
+    ($s:literal) => {
         // SAFETY:
         // - `$s` is guaranteed to be a UTF-8 `str`,
         // - The `concat!` guarantees the input is nul terminated
@@ -126,17 +263,189 @@ macro_rules! c_utf8 {
             $crate::CUtf8::from_str_unchecked(concat!($s, "\0"))
         }
     };
+    ($($s:expr),+ $(,)?) => {
+        // SAFETY: `concat_with_nul` guarantees that `BYTES` is the UTF-8 `$s`
+        // joined, with a single trailing nul byte
+        unsafe {
+            const STRS: &[&str] = &[$($s),+];
+            const BYTES: [u8; $crate::__internal_unstable::concat_len(STRS) + 1] =
+                $crate::__internal_unstable::concat_with_nul(STRS);
+            $crate::__internal_unstable::from_bytes_unchecked(&BYTES)
+        }
+    };
+}
+
+/// Creates a named `static` byte array holding a nul-terminated string
+/// literal, along with a [`&'static CUtf8`](struct.CUtf8.html) view of it.
+///
+/// Unlike [`c_utf8!`](macro.c_utf8.html), the bytes are given a name, so
+/// attributes such as `#[link_section]`, `#[export_name]`, `#[no_mangle]`, and
+/// `#[used]` can be applied to them. This is needed for strings that must live
+/// in specific sections, such as firmware metadata or symbol tables read by C.
+///
+/// The attributes and visibility apply to the byte array, which is the first
+/// name. The second name is a `static` with the same visibility.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// c_utf8_static! {
+///     /// The firmware version, read by the bootloader.
+///     #[cfg_attr(target_os = "linux", link_section = ".fw_meta")]
+///     #[export_name = "fw_version"]
+///     pub static FW_VERSION_BYTES, FW_VERSION = "1.2.3";
+/// }
+///
+/// assert_eq!(&FW_VERSION_BYTES, b"1.2.3\0");
+/// assert_eq!(FW_VERSION.as_str(), "1.2.3");
+/// # }
+/// ```
+///
+/// Like with `c_utf8!`, the literal cannot contain any `\0`:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// c_utf8_static! {
+///     static BYTES, NAME = "Null\0in the middle";
+/// }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! c_utf8_static {
+    ($(#[$attr:meta])* $vis:vis static $bytes:ident, $name:ident = $s:expr;) => {
+        $(#[$attr])*
+        $vis static $bytes: [u8; $s.len() + 1] = $crate::__internal_unstable::with_nul($s);
+
+        // SAFETY: `with_nul` guarantees that `$bytes` is the UTF-8 `$s` with a
+        // single trailing nul byte
+        $vis static $name: &$crate::CUtf8 =
+            unsafe { $crate::__internal_unstable::from_bytes_unchecked(&$bytes) };
+    };
+}
+
+/// Generates a C `main(argc, argv, envp)` entry point that calls a safe Rust
+/// function with the arguments and environment as [`&CUtf8`] iterators.
+///
+/// This is intended for `#![no_main]` and freestanding binaries that are
+/// linked against a C runtime. The given function must have the signature
+/// `fn(CArgs<'static>, CEnv<'static>) -> i32`, and its result is returned
+/// from `main`.
+///
+/// Every argument and environment variable is checked to be UTF-8 before the
+/// function is called. If any of them are not, `main` returns 2 instead. With
+/// the `std` feature, a panic in the function is caught and `main` returns
+/// 101, matching a panicking Rust `main`.
+///
+/// # Examples
+///
+/// ```
+/// #![no_main]
+/// #[macro_use] extern crate c_utf8;
+///
+/// use c_utf8::{CArgs, CEnv};
+///
+/// fn run(mut args: CArgs, env: CEnv) -> i32 {
+///     let program = args.next().unwrap();
+///     assert!(!program.is_empty());
+///
+///     for var in env {
+///         assert!(var.as_str().contains('='));
+///     }
+///     0
+/// }
+///
+/// c_main!(run);
+/// ```
+///
+/// [`&CUtf8`]: struct.CUtf8.html
+#[macro_export]
+macro_rules! c_main {
+    ($f:path) => {
+        #[no_mangle]
+        pub unsafe extern "C" fn main(
+            argc: $crate::__internal_unstable::c_int,
+            argv: *const *const $crate::__internal_unstable::c_char,
+            envp: *const *const $crate::__internal_unstable::c_char,
+        ) -> $crate::__internal_unstable::c_int {
+            $crate::__internal_unstable::c_main(argc, argv, envp, $f)
+        }
+    };
+}
+
+/// Formats into a byte buffer, producing a [`&CUtf8`](struct.CUtf8.html)
+/// without allocating.
+///
+/// The first argument is the `&mut [u8]` buffer, followed by the same arguments
+/// as [`format!`]. This returns a `Result`, with the errors described in
+/// [`CUtf8Writer::format`](struct.CUtf8Writer.html#method.format).
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// let mut buf = [0; 16];
+/// let s = c_utf8_fmt!(&mut buf, "x={}", 42).unwrap();
+/// assert_eq!(s.as_bytes_with_nul(), b"x=42\0");
+///
+/// let mut small = [0; 4];
+/// assert!(c_utf8_fmt!(&mut small, "x={}", 42).is_err());
+/// # }
+/// ```
+///
+/// [`format!`]: https://doc.rust-lang.org/std/macro.format.html
+#[macro_export]
+macro_rules! c_utf8_fmt {
+    ($buf:expr, $($arg:tt)+) => {
+        $crate::CUtf8Writer::format($buf, format_args!($($arg)+))
+    };
 }
 
+mod c_args;
+#[cfg(feature = "alloc")]
+mod c_command;
+mod c_line_writer;
 mod c_utf8;
+mod c_utf8_array;
 #[cfg(feature = "alloc")]
 mod c_utf8_buf;
+mod c_utf8_ptr;
+#[cfg(feature = "alloc")]
+mod c_utf8_trie;
+mod c_utf8_writer;
+mod cmp;
 mod error;
+mod impls;
+mod iter_packed;
+mod nul_terminated;
+mod sanitize;
+mod sync_const_ptr;
+mod utf8_nul_decoder;
+
+#[cfg(feature = "shell")]
+pub mod shell;
 
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub mod trailing_nul;
+
+pub use self::c_args::*;
+#[cfg(feature = "alloc")]
+pub use self::c_command::*;
+pub use self::c_line_writer::*;
 pub use self::c_utf8::*;
+pub use self::c_utf8_array::*;
 #[cfg(feature = "alloc")]
 pub use self::c_utf8_buf::*;
+pub use self::c_utf8_ptr::*;
+#[cfg(feature = "alloc")]
+pub use self::c_utf8_trie::*;
+pub use self::c_utf8_writer::*;
 pub use self::error::*;
+pub use self::iter_packed::*;
+pub use self::nul_terminated::*;
+pub use self::sanitize::*;
+pub use self::sync_const_ptr::*;
+pub use self::utf8_nul_decoder::*;
 
 #[path = "internal.rs"]
 #[doc(hidden)]
@@ -0,0 +1,106 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::ffi::CStr;
+
+use crate::c_utf8::CUtf8;
+#[cfg(feature = "alloc")]
+use crate::c_utf8_buf::CUtf8Buf;
+
+/// A type that may already be terminated by a nul byte.
+///
+/// This is the definition of &#8220;already terminated&#8221; used throughout
+/// this crate, such as when [`CUtf8Buf::from_string`] decides whether to
+/// append a nul byte. It only checks the final byte; interior nul bytes are
+/// not considered.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use c_utf8::NulTerminated;
+///
+/// fn describe<T: NulTerminated + ?Sized>(s: &T) -> &'static str {
+///     if s.is_nul_terminated() {
+///         "terminated"
+///     } else {
+///         "needs a nul byte"
+///     }
+/// }
+///
+/// assert_eq!(describe("hi\0"), "terminated");
+/// assert_eq!(describe(&b"hi"[..]), "needs a nul byte");
+/// assert_eq!(describe(&[b'h', b'i', 0]), "terminated");
+/// ```
+///
+/// [`CUtf8Buf::from_string`]: struct.CUtf8Buf.html#method.from_string
+pub trait NulTerminated {
+    /// Returns whether `self` ends with a nul byte.
+    fn is_nul_terminated(&self) -> bool;
+}
+
+impl<'a, T: NulTerminated + ?Sized> NulTerminated for &'a T {
+    #[inline]
+    fn is_nul_terminated(&self) -> bool {
+        (**self).is_nul_terminated()
+    }
+}
+
+impl NulTerminated for [u8] {
+    #[inline]
+    fn is_nul_terminated(&self) -> bool {
+        self.last() == Some(&0)
+    }
+}
+
+impl<const N: usize> NulTerminated for [u8; N] {
+    #[inline]
+    fn is_nul_terminated(&self) -> bool {
+        self[..].is_nul_terminated()
+    }
+}
+
+impl NulTerminated for str {
+    #[inline]
+    fn is_nul_terminated(&self) -> bool {
+        self.as_bytes().is_nul_terminated()
+    }
+}
+
+impl NulTerminated for CStr {
+    #[inline]
+    fn is_nul_terminated(&self) -> bool {
+        true
+    }
+}
+
+impl NulTerminated for CUtf8 {
+    #[inline]
+    fn is_nul_terminated(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl NulTerminated for Vec<u8> {
+    #[inline]
+    fn is_nul_terminated(&self) -> bool {
+        self[..].is_nul_terminated()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl NulTerminated for String {
+    #[inline]
+    fn is_nul_terminated(&self) -> bool {
+        self.as_str().is_nul_terminated()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl NulTerminated for CUtf8Buf {
+    #[inline]
+    fn is_nul_terminated(&self) -> bool {
+        true
+    }
+}
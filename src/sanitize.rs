@@ -0,0 +1,382 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use crate::c_utf8::CUtf8;
+
+/// A named validation policy, which [`SanitizedCUtf8`] records in its type.
+///
+/// Policies are implemented on marker types, usually empty enums. Only the
+/// crate that owns a marker type can implement `Policy` for it, so a
+/// `&SanitizedCUtf8<P>` proves that the string passed the rules of `P` in
+/// particular, and not just those of any sanitizer.
+///
+/// [`SanitizedCUtf8`]: struct.SanitizedCUtf8.html
+pub trait Policy {
+    /// The rules that strings must pass.
+    const SANITIZER: Sanitizer;
+}
+
+/// A [`CUtf8`](struct.CUtf8.html) that has been checked against the
+/// [`Policy`](trait.Policy.html) `P`.
+///
+/// APIs wrapping fragile C interfaces can take `&SanitizedCUtf8<P>` to require
+/// proof that their input was validated by their policy, instead of
+/// validating again at every layer. Strings checked against a different
+/// policy have a different type.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// use c_utf8::{Policy, SanitizedCUtf8, Sanitizer};
+///
+/// enum Hostname {}
+///
+/// impl Policy for Hostname {
+///     const SANITIZER: Sanitizer = Sanitizer::new()
+///         .max_len(64)
+///         .ascii_only()
+///         .no_shell_metachars();
+/// }
+///
+/// fn set_hostname(name: &SanitizedCUtf8<Hostname>) {
+///     // ... pass `name.as_ptr()` to C ...
+/// }
+///
+/// let name = SanitizedCUtf8::new(c_utf8!("build-01")).unwrap();
+/// set_hostname(name);
+///
+/// assert!(SanitizedCUtf8::<Hostname>::new(c_utf8!("x; rm -rf /")).is_err());
+/// # }
+/// ```
+///
+/// A string checked against a more lenient policy is not accepted:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// use c_utf8::{Policy, SanitizedCUtf8, Sanitizer};
+///
+/// enum Hostname {}
+/// enum Anything {}
+///
+/// impl Policy for Hostname {
+///     const SANITIZER: Sanitizer = Sanitizer::new().ascii_only();
+/// }
+///
+/// impl Policy for Anything {
+///     const SANITIZER: Sanitizer = Sanitizer::new();
+/// }
+///
+/// fn set_hostname(name: &SanitizedCUtf8<Hostname>) {}
+///
+/// let name = SanitizedCUtf8::<Anything>::new(c_utf8!("x; rm -rf /")).unwrap();
+/// set_hostname(name);
+/// # }
+/// ```
+#[repr(transparent)]
+pub struct SanitizedCUtf8<P: Policy> {
+    policy: PhantomData<fn() -> P>,
+    inner: CUtf8,
+}
+
+impl<P: Policy> Deref for SanitizedCUtf8<P> {
+    type Target = CUtf8;
+
+    #[inline]
+    fn deref(&self) -> &CUtf8 {
+        &self.inner
+    }
+}
+
+impl<P: Policy> AsRef<CUtf8> for SanitizedCUtf8<P> {
+    #[inline]
+    fn as_ref(&self) -> &CUtf8 {
+        &self.inner
+    }
+}
+
+impl<P: Policy> PartialEq for SanitizedCUtf8<P> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<P: Policy> Eq for SanitizedCUtf8<P> {}
+
+impl<P: Policy> PartialOrd for SanitizedCUtf8<P> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: Policy> Ord for SanitizedCUtf8<P> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.cmp(&other.inner)
+    }
+}
+
+impl<P: Policy> Hash for SanitizedCUtf8<P> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state)
+    }
+}
+
+impl<P: Policy> fmt::Debug for SanitizedCUtf8<P> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<P: Policy> fmt::Display for SanitizedCUtf8<P> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<P: Policy> SanitizedCUtf8<P> {
+    /// Checks `s` against the policy `P`, returning it as sanitized on
+    /// success.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule of [`P::SANITIZER`](trait.Policy.html) that `s`
+    /// breaks, as checked by [`Sanitizer::check`].
+    ///
+    /// [`Sanitizer::check`]: struct.Sanitizer.html#method.check
+    #[inline]
+    pub fn new(s: &CUtf8) -> Result<&SanitizedCUtf8<P>, SanitizeError> {
+        P::SANITIZER.check(s)?;
+        // SAFETY: `SanitizedCUtf8` is `#[repr(transparent)]` over `CUtf8`
+        Ok(unsafe { &*(s as *const CUtf8 as *const SanitizedCUtf8<P>) })
+    }
+
+    /// Returns `self` as a plain C string.
+    #[inline]
+    pub fn as_c_utf8(&self) -> &CUtf8 {
+        &self.inner
+    }
+}
+
+/// The error for strings rejected by a [`Sanitizer`](struct.Sanitizer.html).
+///
+/// Positions are byte offsets of the offending character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SanitizeError {
+    /// An error indicating that there was a nul byte before the end.
+    Nul {
+        /// The position of the nul byte.
+        position: usize,
+    },
+    /// An error indicating that the string was longer than the maximum.
+    TooLong {
+        /// The maximum number of bytes allowed.
+        max: usize,
+    },
+    /// An error indicating that the string contained a control character.
+    ControlChar {
+        /// The position of the control character.
+        position: usize,
+    },
+    /// An error indicating that the string contained a non-ASCII character.
+    NonAscii {
+        /// The position of the non-ASCII character.
+        position: usize,
+    },
+    /// An error indicating that the string contained a shell metacharacter.
+    ShellMetachar {
+        /// The position of the shell metacharacter.
+        position: usize,
+    },
+    /// An error indicating that a custom rule rejected the string.
+    Rule,
+}
+
+impl fmt::Display for SanitizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SanitizeError::Nul { position } => write!(f, "Nul byte at position {}", position),
+            SanitizeError::TooLong { max } => write!(f, "String is longer than {} bytes", max),
+            SanitizeError::ControlChar { position } => {
+                write!(f, "Control character at position {}", position)
+            }
+            SanitizeError::NonAscii { position } => {
+                write!(f, "Non-ASCII character at position {}", position)
+            }
+            SanitizeError::ShellMetachar { position } => {
+                write!(f, "Shell metacharacter at position {}", position)
+            }
+            SanitizeError::Rule => "String was rejected by a custom rule".fmt(f),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "error_in_core"))]
+impl crate::error::StdError for SanitizeError {}
+
+/// A builder of validation rules, which are given a name by implementing
+/// [`Policy`](trait.Policy.html).
+///
+/// A sanitizer always rejects nul bytes before the end of the string. Every
+/// other rule is opt-in. The builder methods are `const`, so policies can be
+/// declared as constants.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sanitizer {
+    max_len: Option<usize>,
+    no_control_chars: bool,
+    ascii_only: bool,
+    no_shell_metachars: bool,
+    rule: Option<fn(&CUtf8) -> bool>,
+}
+
+impl Sanitizer {
+    /// Creates a new sanitizer that only rejects interior nul bytes.
+    #[inline]
+    pub const fn new() -> Sanitizer {
+        Sanitizer {
+            max_len: None,
+            no_control_chars: false,
+            ascii_only: false,
+            no_shell_metachars: false,
+            rule: None,
+        }
+    }
+
+    /// Rejects strings longer than `max` bytes, disregarding the trailing nul
+    /// byte.
+    #[inline]
+    pub const fn max_len(mut self, max: usize) -> Sanitizer {
+        self.max_len = Some(max);
+        self
+    }
+
+    /// Rejects strings containing control characters, such as `\n`, `\t`, or
+    /// terminal escape sequences.
+    #[inline]
+    pub const fn no_control_chars(mut self) -> Sanitizer {
+        self.no_control_chars = true;
+        self
+    }
+
+    /// Rejects strings containing non-ASCII characters.
+    #[inline]
+    pub const fn ascii_only(mut self) -> Sanitizer {
+        self.ascii_only = true;
+        self
+    }
+
+    /// Rejects strings containing whitespace or any of the shell
+    /// metacharacters ``| & ; < > ( ) $ ` \ " ' * ? [ ] # ~ ! { }``.
+    #[inline]
+    pub const fn no_shell_metachars(mut self) -> Sanitizer {
+        self.no_shell_metachars = true;
+        self
+    }
+
+    /// Rejects strings for which `rule` returns `false`.
+    ///
+    /// This is checked after all other rules, and replaces any previous custom
+    /// rule.
+    #[inline]
+    pub const fn rule(mut self, rule: fn(&CUtf8) -> bool) -> Sanitizer {
+        self.rule = Some(rule);
+        self
+    }
+
+    /// Checks `s` against the rules of `self`.
+    ///
+    /// Use [`SanitizedCUtf8::new`] instead to keep proof of the check in the
+    /// type of the string.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate c_utf8; fn main() {
+    /// use c_utf8::{SanitizeError, Sanitizer};
+    ///
+    /// let policy = Sanitizer::new()
+    ///     .no_control_chars()
+    ///     .rule(|s| !s.as_str().starts_with('-'));
+    ///
+    /// assert!(policy.check(c_utf8!("file.txt")).is_ok());
+    /// assert_eq!(
+    ///     policy.check(c_utf8!("a\nb")),
+    ///     Err(SanitizeError::ControlChar { position: 1 }),
+    /// );
+    /// assert_eq!(policy.check(c_utf8!("-rf")), Err(SanitizeError::Rule));
+    /// # }
+    /// ```
+    ///
+    /// [`SanitizedCUtf8::new`]: struct.SanitizedCUtf8.html#method.new
+    pub fn check(&self, s: &CUtf8) -> Result<(), SanitizeError> {
+        let string = s.as_str();
+
+        if let Some(max) = self.max_len {
+            if string.len() > max {
+                return Err(SanitizeError::TooLong { max });
+            }
+        }
+
+        for (position, c) in string.char_indices() {
+            if c == '\0' {
+                return Err(SanitizeError::Nul { position });
+            }
+            if self.no_control_chars && c.is_control() {
+                return Err(SanitizeError::ControlChar { position });
+            }
+            if self.ascii_only && !c.is_ascii() {
+                return Err(SanitizeError::NonAscii { position });
+            }
+            if self.no_shell_metachars && is_shell_metachar(c) {
+                return Err(SanitizeError::ShellMetachar { position });
+            }
+        }
+
+        if let Some(rule) = self.rule {
+            if !rule(s) {
+                return Err(SanitizeError::Rule);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_shell_metachar(c: char) -> bool {
+    matches!(
+        c,
+        ' ' | '\t'
+            | '\n'
+            | '|'
+            | '&'
+            | ';'
+            | '<'
+            | '>'
+            | '('
+            | ')'
+            | '$'
+            | '`'
+            | '\\'
+            | '"'
+            | '\''
+            | '*'
+            | '?'
+            | '['
+            | ']'
+            | '#'
+            | '~'
+            | '!'
+            | '{'
+            | '}'
+    )
+}
@@ -0,0 +1,355 @@
+//! Splitting and joining command lines of C string arguments.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::{fmt, mem};
+
+use crate::c_utf8::CUtf8;
+use crate::c_utf8_buf::CUtf8Buf;
+
+/// The error for splitting a command line into arguments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitError {
+    /// An error indicating that the command line contained a nul byte.
+    Nul,
+    /// An error indicating that a quoted section was never closed.
+    UnclosedQuote,
+    /// An error indicating that the command line ended with an escaping
+    /// backslash.
+    TrailingBackslash,
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SplitError::Nul => "Command line contains a nul byte".fmt(f),
+            SplitError::UnclosedQuote => "Missing closing quote".fmt(f),
+            SplitError::TrailingBackslash => "Missing character after backslash".fmt(f),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "error_in_core"))]
+impl crate::error::StdError for SplitError {}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    /// Between words.
+    Delimiter,
+    /// Inside of an unquoted part of a word.
+    Unquoted,
+    /// Inside of a `'...'` part of a word.
+    SingleQuoted,
+    /// Inside of a `"..."` part of a word.
+    DoubleQuoted,
+    /// Inside of a `#` comment.
+    Comment,
+}
+
+/// Splits `line` into arguments following the quoting rules of the
+/// [POSIX shell](https://pubs.opengroup.org/onlinepubs/9699919799/utilities/V3_chap02.html#tag_18_02).
+///
+/// Words are separated by unquoted spaces, tabs, and newlines. Quotes and
+/// backslashes are removed as the shell would, and an unquoted `#` at the start
+/// of a word begins a comment that runs until the end of the line. No
+/// expansions (`$VAR`, `` `cmd` ``, `*`, `~`) are performed.
+///
+/// Each argument is returned as a nul-terminated [`CUtf8Buf`], ready to be
+/// handed to `exec`-style C APIs.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use c_utf8::shell;
+///
+/// let args = shell::split_posix(r#"grep -e 'a b' "c \"d\"" e\ f"#).unwrap();
+///
+/// assert_eq!(args.len(), 5);
+/// assert_eq!(args[2].as_str(), "a b");
+/// assert_eq!(args[3].as_str(), "c \"d\"");
+/// assert_eq!(args[4].as_bytes_with_nul(), b"e f\0");
+/// ```
+///
+/// Malformed command lines are rejected:
+///
+/// ```
+/// use c_utf8::shell::{self, SplitError};
+///
+/// assert_eq!(shell::split_posix("echo 'oops"), Err(SplitError::UnclosedQuote));
+/// assert_eq!(shell::split_posix("echo \0"), Err(SplitError::Nul));
+/// ```
+///
+/// [`CUtf8Buf`]: ../struct.CUtf8Buf.html
+pub fn split_posix(line: &str) -> Result<Vec<CUtf8Buf>, SplitError> {
+    if line.as_bytes().contains(&0) {
+        return Err(SplitError::Nul);
+    }
+
+    let mut args = Vec::new();
+    let mut word = String::new();
+    let mut state = State::Delimiter;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        state = match state {
+            State::Delimiter | State::Unquoted => match c {
+                ' ' | '\t' | '\n' => {
+                    if state == State::Unquoted {
+                        args.push(CUtf8Buf::from_string(mem::take(&mut word)));
+                    }
+                    State::Delimiter
+                }
+                '#' if state == State::Delimiter => State::Comment,
+                '\'' => State::SingleQuoted,
+                '"' => State::DoubleQuoted,
+                '\\' => match chars.next() {
+                    // Line continuation
+                    Some('\n') => state,
+                    Some(c) => {
+                        word.push(c);
+                        State::Unquoted
+                    }
+                    None => return Err(SplitError::TrailingBackslash),
+                },
+                c => {
+                    word.push(c);
+                    State::Unquoted
+                }
+            },
+            State::SingleQuoted => match c {
+                '\'' => State::Unquoted,
+                c => {
+                    word.push(c);
+                    state
+                }
+            },
+            State::DoubleQuoted => match c {
+                '"' => State::Unquoted,
+                '\\' => match chars.next() {
+                    Some('\n') => state,
+                    Some(c @ '$') | Some(c @ '`') | Some(c @ '"') | Some(c @ '\\') => {
+                        word.push(c);
+                        state
+                    }
+                    Some(c) => {
+                        word.push('\\');
+                        word.push(c);
+                        state
+                    }
+                    None => return Err(SplitError::UnclosedQuote),
+                },
+                c => {
+                    word.push(c);
+                    state
+                }
+            },
+            State::Comment => match c {
+                '\n' => State::Delimiter,
+                _ => state,
+            },
+        };
+    }
+
+    match state {
+        State::SingleQuoted | State::DoubleQuoted => Err(SplitError::UnclosedQuote),
+        State::Unquoted => {
+            args.push(CUtf8Buf::from_string(word));
+            Ok(args)
+        }
+        State::Delimiter | State::Comment => Ok(args),
+    }
+}
+
+/// Joins `args` into a single command line following the quoting rules of
+/// [`CommandLineToArgvW`](https://learn.microsoft.com/en-us/windows/win32/api/shellapi/nf-shellapi-commandlinetoargvw),
+/// as expected by `CreateProcess`-style C APIs.
+///
+/// The first argument is treated as the program name, which Windows parses
+/// without any escaping, so it is only surrounded by quotes when it contains
+/// whitespace. It therefore should not contain `"`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// use c_utf8::shell;
+///
+/// let args = [
+///     c_utf8!("C:\\Program Files\\app.exe"),
+///     c_utf8!("plain"),
+///     c_utf8!("with space"),
+///     c_utf8!("say \"hi\""),
+///     c_utf8!("C:\\dir\\"),
+/// ];
+/// let line = shell::join_windows(args.iter().cloned());
+///
+/// assert_eq!(
+///     line.as_str(),
+///     r#""C:\Program Files\app.exe" plain "with space" "say \"hi\"" C:\dir\"#,
+/// );
+///
+/// let split = shell::split_windows(line.as_str()).unwrap();
+/// assert_eq!(split.len(), args.len());
+/// for (a, b) in split.iter().zip(&args) {
+///     assert_eq!(a, *b);
+/// }
+/// # }
+/// ```
+pub fn join_windows<I>(args: I) -> CUtf8Buf
+where
+    I: IntoIterator,
+    I::Item: Borrow<CUtf8>,
+{
+    let mut line = String::new();
+    let mut args = args.into_iter();
+
+    if let Some(program) = args.next() {
+        let program = program.borrow().as_str();
+        if program.is_empty() || program.contains(|c| c == ' ' || c == '\t') {
+            line.push('"');
+            line.push_str(program);
+            line.push('"');
+        } else {
+            line.push_str(program);
+        }
+    }
+
+    for arg in args {
+        let arg = arg.borrow().as_str();
+        line.push(' ');
+
+        let needs_quotes =
+            arg.is_empty() || arg.contains(|c| matches!(c, ' ' | '\t' | '\n' | '\x0B' | '"'));
+        if !needs_quotes {
+            line.push_str(arg);
+            continue;
+        }
+
+        line.push('"');
+        let mut backslashes = 0;
+        for c in arg.chars() {
+            match c {
+                '\\' => backslashes += 1,
+                '"' => {
+                    // Escape the preceding backslashes and the quote itself
+                    push_backslashes(&mut line, backslashes * 2 + 1);
+                    line.push('"');
+                    backslashes = 0;
+                }
+                c => {
+                    push_backslashes(&mut line, backslashes);
+                    line.push(c);
+                    backslashes = 0;
+                }
+            }
+        }
+        // Escape trailing backslashes so that they don't escape the final quote
+        push_backslashes(&mut line, backslashes * 2);
+        line.push('"');
+    }
+
+    CUtf8Buf::from_string(line)
+}
+
+fn push_backslashes(s: &mut String, n: usize) {
+    s.extend((0..n).map(|_| '\\'));
+}
+
+/// Splits `line` into arguments following the parsing rules of
+/// [`CommandLineToArgvW`](https://learn.microsoft.com/en-us/windows/win32/api/shellapi/nf-shellapi-commandlinetoargvw).
+///
+/// This is the inverse of [`join_windows`](fn.join_windows.html). The first
+/// argument is parsed as the program name: it ends at the first unquoted space
+/// or tab and backslashes within it are taken literally.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use c_utf8::shell;
+///
+/// let args = shell::split_windows(r#"app.exe a\\"b c" "d""e" f\g"#).unwrap();
+///
+/// assert_eq!(args.len(), 4);
+/// assert_eq!(args[1].as_str(), r"a\b c");
+/// assert_eq!(args[2].as_str(), r#"d"e"#);
+/// assert_eq!(args[3].as_str(), r"f\g");
+/// ```
+pub fn split_windows(line: &str) -> Result<Vec<CUtf8Buf>, SplitError> {
+    if line.as_bytes().contains(&0) {
+        return Err(SplitError::Nul);
+    }
+
+    let mut args = Vec::new();
+    let mut word = String::new();
+    let mut chars = line.chars().peekable();
+
+    if chars.peek().is_none() {
+        return Ok(args);
+    }
+
+    // The program name has no escape characters
+    let mut in_quotes = false;
+    for c in &mut chars {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ' ' | '\t' if !in_quotes => break,
+            c => word.push(c),
+        }
+    }
+    args.push(CUtf8Buf::from_string(mem::take(&mut word)));
+
+    let mut in_quotes = false;
+    let mut in_word = false;
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' if !in_quotes => {
+                if in_word {
+                    args.push(CUtf8Buf::from_string(mem::take(&mut word)));
+                    in_word = false;
+                }
+                continue;
+            }
+            '\\' => {
+                let mut backslashes = 1;
+                while chars.peek() == Some(&'\\') {
+                    chars.next();
+                    backslashes += 1;
+                }
+                if chars.peek() == Some(&'"') {
+                    push_backslashes(&mut word, backslashes / 2);
+                    // An odd number of backslashes escapes the quote
+                    if backslashes % 2 == 1 {
+                        chars.next();
+                        word.push('"');
+                    }
+                } else {
+                    push_backslashes(&mut word, backslashes);
+                }
+            }
+            '"' if in_quotes => {
+                // Two consecutive quotes produce one literal quote
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    word.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' => in_quotes = true,
+            c => word.push(c),
+        }
+        in_word = true;
+    }
+
+    if in_word {
+        args.push(CUtf8Buf::from_string(word));
+    }
+    Ok(args)
+}
@@ -0,0 +1,72 @@
+use core::ffi::{c_char, CStr};
+
+use crate::c_utf8::CUtf8;
+
+/// A `Sync` pointer to the start of a static C string, for use in `static`
+/// tables consumed by C.
+///
+/// Raw pointers are not `Sync`, so they cannot be placed in a `static`
+/// directly. This type is `#[repr(transparent)]` over `*const c_char`, which
+/// makes it layout-compatible with `const char*` fields of C structs.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # #[macro_use] extern crate c_utf8; fn main() {
+/// use c_utf8::SyncConstPtr;
+///
+/// #[repr(C)]
+/// struct LongOption {
+///     name: SyncConstPtr,
+///     has_arg: i32,
+/// }
+///
+/// static OPTIONS: [LongOption; 2] = [
+///     LongOption { name: SyncConstPtr::new(c_utf8!("help")), has_arg: 0 },
+///     LongOption { name: SyncConstPtr::new(c_utf8!("output")), has_arg: 1 },
+/// ];
+///
+/// assert_eq!(OPTIONS[1].name.as_c_utf8().as_str(), "output");
+/// # }
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SyncConstPtr(*const c_char);
+
+// SAFETY: The pointer can only be created from an immutable `&'static CUtf8`
+unsafe impl Sync for SyncConstPtr {}
+unsafe impl Send for SyncConstPtr {}
+
+impl From<&'static CUtf8> for SyncConstPtr {
+    #[inline]
+    fn from(c: &'static CUtf8) -> SyncConstPtr {
+        SyncConstPtr::new(c)
+    }
+}
+
+impl SyncConstPtr {
+    /// Creates a new pointer to the start of `c`.
+    #[inline]
+    pub const fn new(c: &'static CUtf8) -> SyncConstPtr {
+        SyncConstPtr(c.as_ptr())
+    }
+
+    /// Returns the raw pointer to the start of the C string.
+    #[inline]
+    pub const fn as_ptr(self) -> *const c_char {
+        self.0
+    }
+
+    /// Returns the C string being pointed to.
+    #[inline]
+    pub fn as_c_utf8(self) -> &'static CUtf8 {
+        // SAFETY: The pointer only comes from a `&'static CUtf8`, whose bytes
+        // are immutable and outlive every use, so `strlen` stops at a nul
+        // byte within them (the terminator at the latest). Nul bytes lie on
+        // `char` boundaries, so the bytes before it are UTF-8 that was
+        // validated when that `CUtf8` was created.
+        unsafe { CUtf8::from_c_str_unchecked(CStr::from_ptr(self.0)) }
+    }
+}
@@ -0,0 +1,98 @@
+//! Serde adapters for strings that carry their nul terminator, such as those in
+//! binary payloads produced by C.
+//!
+//! Use with `#[serde(with = "c_utf8::trailing_nul")]` on a
+//! [`CUtf8Buf`](../struct.CUtf8Buf.html) field. When deserializing, either a
+//! string or bytes are accepted, with or without a single trailing nul byte.
+//! When serializing, the trailing nul byte is kept so that the payload stays
+//! readable by C.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate c_utf8;
+//! # extern crate serde;
+//! use c_utf8::{trailing_nul, CUtf8Buf};
+//! use serde::de::value::{BytesDeserializer, Error};
+//!
+//! let de = BytesDeserializer::<Error>::new(b"from C\0");
+//! let s: CUtf8Buf = trailing_nul::deserialize(de).unwrap();
+//!
+//! assert_eq!(s.as_bytes_with_nul(), b"from C\0");
+//!
+//! let de = BytesDeserializer::<Error>::new(b"no nul");
+//! let s: CUtf8Buf = trailing_nul::deserialize(de).unwrap();
+//!
+//! assert_eq!(s.as_bytes_with_nul(), b"no nul\0");
+//!
+//! let de = BytesDeserializer::<Error>::new(b"interior\0nul\0");
+//! assert!(trailing_nul::deserialize(de).is_err());
+//! # fn main() {}
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str;
+
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::ser::Serializer;
+
+use crate::c_utf8::CUtf8;
+use crate::c_utf8_buf::CUtf8Buf;
+
+/// Serializes `value` as a string, including its trailing nul byte.
+#[inline]
+pub fn serialize<S: Serializer>(value: &CUtf8, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(value.as_str_with_nul())
+}
+
+/// Deserializes a string or bytes with an optional single trailing nul byte.
+#[inline]
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<CUtf8Buf, D::Error> {
+    deserializer.deserialize_str(TrailingNulVisitor)
+}
+
+struct TrailingNulVisitor;
+
+impl TrailingNulVisitor {
+    fn finish<E: de::Error>(self, s: String) -> Result<CUtf8Buf, E> {
+        // Only allow for a nul byte in the final position
+        match s.bytes().position(|b| b == 0) {
+            Some(i) if i != s.len() - 1 => Err(E::invalid_value(Unexpected::Str(&s), &self)),
+            _ => Ok(CUtf8Buf::from_string(s)),
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for TrailingNulVisitor {
+    type Value = CUtf8Buf;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string with no nul bytes before an optional terminator")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<CUtf8Buf, E> {
+        self.finish(s.into())
+    }
+
+    #[inline]
+    fn visit_string<E: de::Error>(self, s: String) -> Result<CUtf8Buf, E> {
+        self.finish(s)
+    }
+
+    fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<CUtf8Buf, E> {
+        match str::from_utf8(b) {
+            Ok(s) => self.visit_str(s),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(b), &self)),
+        }
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, b: Vec<u8>) -> Result<CUtf8Buf, E> {
+        match String::from_utf8(b) {
+            Ok(s) => self.finish(s),
+            Err(err) => Err(E::invalid_value(Unexpected::Bytes(err.as_bytes()), &self)),
+        }
+    }
+}
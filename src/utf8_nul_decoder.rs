@@ -0,0 +1,203 @@
+use core::str;
+
+#[cfg(feature = "alloc")]
+use crate::c_utf8_buf::CUtf8Buf;
+use crate::error::Error;
+
+/// An incremental validator for UTF-8 without nul bytes that arrives in
+/// arbitrary chunks, such as from a socket or a C callback.
+///
+/// Each chunk is validated once, and characters split across chunk boundaries
+/// are held by the decoder until they are complete. This avoids re-validating
+/// everything received so far whenever a new chunk arrives.
+///
+/// No allocations are made, so this is available without the `alloc` feature.
+///
+/// # Examples
+///
+/// Basic usage, decoding into a fixed-size
+/// [`CUtf8Array`](struct.CUtf8Array.html):
+///
+/// ```
+/// use c_utf8::{CUtf8Array, Error, Utf8NulDecoder};
+///
+/// let mut decoder = Utf8NulDecoder::new();
+/// let mut name = CUtf8Array::<16>::new();
+///
+/// // "é" is split across the two chunks
+/// assert_eq!(decoder.decode(b"caf\xC3", |s| name.push_str(s)), Ok(3));
+/// assert_eq!(decoder.pending(), b"\xC3");
+///
+/// assert_eq!(decoder.decode(b"\xA9!", |s| name.push_str(s)), Ok(2));
+/// assert!(decoder.pending().is_empty());
+///
+/// decoder.finish().unwrap();
+/// assert_eq!(name.as_bytes_with_nul(), "café!\0".as_bytes());
+///
+/// let err = decoder.decode(b"\0", |s| name.push_str(s)).unwrap_err();
+/// assert_eq!(err, Error::InteriorNul { position: 0 });
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Utf8NulDecoder {
+    /// The start of an incomplete character from a previous chunk.
+    pending: [u8; 4],
+    pending_len: u8,
+}
+
+impl Utf8NulDecoder {
+    /// Creates a new decoder with nothing pending.
+    #[inline]
+    pub const fn new() -> Utf8NulDecoder {
+        Utf8NulDecoder {
+            pending: [0; 4],
+            pending_len: 0,
+        }
+    }
+
+    /// Returns the bytes of an incomplete character at the end of the last
+    /// chunk, which are waiting for the rest of the character.
+    #[inline]
+    pub fn pending(&self) -> &[u8] {
+        &self.pending[..self.pending_len as usize]
+    }
+
+    /// Validates `chunk`, passing each complete run of text to `f`.
+    ///
+    /// Returns the number of bytes of `chunk` that were passed to `f`. Any
+    /// remaining bytes are the start of an incomplete character, which are
+    /// kept until the next call and can be inspected with
+    /// [`pending`](#method.pending).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InteriorNul`] if `chunk` contains a nul byte, or
+    /// [`Error::Utf8`] if it is not encoded as UTF-8. The text before the
+    /// offending byte is passed to `f` first. Positions are relative to
+    /// `chunk`, except that a [`Utf8`] error is relative to the end of any
+    /// character completed from the previous chunk.
+    ///
+    /// Errors returned by `f` are returned as is, and stop decoding.
+    ///
+    /// Pending bytes are discarded on error, so the decoder can be reused.
+    ///
+    /// [`Error::InteriorNul`]: enum.Error.html#variant.InteriorNul
+    /// [`Error::Utf8`]:        enum.Error.html#variant.Utf8
+    /// [`Utf8`]:               enum.Error.html#variant.Utf8
+    pub fn decode<F>(&mut self, chunk: &[u8], mut f: F) -> Result<usize, Error>
+    where
+        F: FnMut(&str) -> Result<(), Error>,
+    {
+        let mut start = 0;
+
+        if self.pending_len > 0 {
+            let len = self.pending_len as usize;
+            let take = Ord::min(utf8_width(self.pending[0]) - len, chunk.len());
+
+            let mut seq = self.pending;
+            seq[len..len + take].copy_from_slice(&chunk[..take]);
+            self.pending_len = 0;
+
+            match str::from_utf8(&seq[..len + take]) {
+                // Multi-byte characters never contain nul bytes
+                Ok(s) => f(s)?,
+                Err(err) if err.error_len().is_none() => {
+                    self.pending = seq;
+                    self.pending_len = (len + take) as u8;
+                    return Ok(0);
+                }
+                Err(err) => return Err(Error::Utf8(err)),
+            }
+            start = take;
+        }
+
+        let rest = &chunk[start..];
+        let (valid, err) = match str::from_utf8(rest) {
+            Ok(s) => (s, None),
+            Err(err) => {
+                // SAFETY: the bytes up to `valid_up_to` are UTF-8
+                let s = unsafe { str::from_utf8_unchecked(&rest[..err.valid_up_to()]) };
+                (s, Some(err))
+            }
+        };
+
+        if let Some(nul) = valid.bytes().position(|b| b == 0) {
+            if nul > 0 {
+                f(&valid[..nul])?;
+            }
+            return Err(Error::InteriorNul {
+                position: start + nul,
+            });
+        }
+        if !valid.is_empty() {
+            f(valid)?;
+        }
+
+        match err {
+            None => Ok(chunk.len()),
+            Some(err) if err.error_len().is_none() => {
+                let tail = &rest[valid.len()..];
+                self.pending[..tail.len()].copy_from_slice(tail);
+                self.pending_len = tail.len() as u8;
+                Ok(start + valid.len())
+            }
+            Some(err) => Err(Error::Utf8(err)),
+        }
+    }
+
+    /// Validates `chunk` and appends it to `buf`, as with
+    /// [`decode`](#method.decode).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use c_utf8::{CUtf8Buf, Utf8NulDecoder};
+    ///
+    /// let mut decoder = Utf8NulDecoder::new();
+    /// let mut buf = CUtf8Buf::new();
+    ///
+    /// for chunk in [&b"caf\xC3"[..], b"\xA9!"] {
+    ///     decoder.decode_into(chunk, &mut buf).unwrap();
+    /// }
+    /// decoder.finish().unwrap();
+    ///
+    /// assert_eq!(buf.as_str(), "café!");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn decode_into(&mut self, chunk: &[u8], buf: &mut CUtf8Buf) -> Result<usize, Error> {
+        self.decode(chunk, |s| {
+            buf.push_str(s);
+            Ok(())
+        })
+    }
+
+    /// Checks that the input did not end partway through a character, and
+    /// resets the decoder.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Utf8`](enum.Error.html#variant.Utf8) if there are
+    /// [`pending`](#method.pending) bytes.
+    #[inline]
+    pub fn finish(&mut self) -> Result<(), Error> {
+        let len = self.pending_len as usize;
+        self.pending_len = 0;
+        match str::from_utf8(&self.pending[..len]) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Error::Utf8(err)),
+        }
+    }
+}
+
+/// Returns the width of the character started by `first`, which is a valid
+/// leading byte of a multi-byte character.
+#[inline]
+fn utf8_width(first: u8) -> usize {
+    match first {
+        0x00..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+}